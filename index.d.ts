@@ -9,6 +9,8 @@ export interface FeaturesFlag {
   newTarget: boolean
   objectSuper: boolean
   typeofSymbol: boolean
+  duplicateKeys: boolean
+  instanceOf: boolean
   forOf: boolean
   classes: boolean
   spread: boolean
@@ -17,6 +19,15 @@ export interface FeaturesFlag {
   computedProperties: boolean
   shorthandProperties: boolean
   stickyRegex: boolean
+  unicodeRegex: boolean
+  dotAllRegex: boolean
+  namedCapturingGroupsRegex: boolean
+  unicodePropertyRegex: boolean
+  lookbehindRegex: boolean
+  hasIndicesRegex: boolean
+  unicodeSetsRegex: boolean
+  regexModifiers: boolean
+  duplicateNamedGroupsRegex: boolean
   templateLiterals: boolean
   parameters: boolean
   arrowFunctions: boolean
@@ -25,11 +36,42 @@ export interface FeaturesFlag {
   classStaticBlock: boolean
   privateMethods: boolean
   asyncToGenerator: boolean
+  trailingFunctionCommas: boolean
   logicalAssignmentOperators: boolean
   nullishCoalescing: boolean
   objectRestSpread: boolean
+  asyncGeneratorFunctions: boolean
+  forAwait: boolean
   optionalChaining: boolean
   optionalCatchBinding: boolean
+  jsonStrings: boolean
+  binaryLiterals: boolean
+  octalLiterals: boolean
+  numericSeparator: boolean
+  bigInt: boolean
+  unicodeEscapes: boolean
+  memberExpressionLiterals: boolean
+  propertyLiterals: boolean
+  propertyMutators: boolean
+  trailingLiteralCommas: boolean
+  es5Builtins: boolean
+  dynamicImport: boolean
+  importMeta: boolean
+  exportNamespaceFrom: boolean
+  topLevelAwait: boolean
+  importAttributes: boolean
+  hashbang: boolean
+  privateFields: boolean
+  staticPrivate: boolean
+  privatePropertyInObject: boolean
+  autoAccessors: boolean
+  decorators: boolean
+  explicitResourceManagement: boolean
+  bugfixSafariBlockShadowing: boolean
+  bugfixSafariIdDestructuringCollision: boolean
+  bugfixEdgeDefaultParameters: boolean
+  bugfixTaggedTemplateCaching: boolean
+  bugfixV8SpreadParametersInOptionalChaining: boolean
 }
 export interface Line {
   l: number
@@ -41,17 +83,42 @@ export interface Detail {
   e: number
   ls: Line
   le: Line
+  transformable: boolean
+  origin?: string
+  kind?: string
+  since?: string
+  guarded?: boolean
+  excluded?: boolean
+  lowConfidence?: boolean
+}
+export interface SyntaxOptions {
+  syntax?: string
+  jsx?: boolean
+  tsx?: boolean
+  decorators?: boolean
 }
 export interface ParseOptions {
   target?: string
-  browserslist: string
+  tsconfig?: string
+  syntax?: SyntaxOptions
+  browserslist?: string | Array<string> | Record<string, string>
+  path?: string
+  env?: string
   filename: string
   code: string
+  skipRuntime?: boolean
+  webApis?: boolean
+  node?: string
+  bugfixes?: boolean
+  include?: Array<string>
+  exclude?: Array<string>
+  forceAllTransforms?: boolean
 }
 export interface DetectResult {
   features: FeaturesFlag
   esVersions: Record<string, boolean>
   details: Array<Detail>
+  query?: Array<string>
 }
 export function detect(options: ParseOptions): DetectResult
 export interface LookupOptions {
@@ -0,0 +1,344 @@
+use std::collections::HashSet;
+
+use swc_core::common::Span;
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{noop_visit_type, Visit, VisitWith};
+
+// Engine bugs preset-env works around with `bugfixes`, instead of transforming the whole feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BugfixFeature {
+  // function f(a) { { let a } }, Safari 10
+  SafariBlockShadowing,
+  // (function a({ a }) {}), Safari 10 - 16.2
+  SafariIdDestructuringCollision,
+  // var a; (a = 1) => a, Edge 16 - 17
+  EdgeDefaultParameters,
+  // tag`a`, Safari 12
+  TaggedTemplateCaching,
+  // a?.(...b), Chrome 80 - 90
+  V8SpreadParametersInOptionalChaining,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BugfixMatch {
+  pub feature: BugfixFeature,
+  pub span: Span,
+}
+
+// Every code pattern hitting an engine bug in `program`, whether or not the targets have it
+pub fn analyze_bugfixes(program: &Program) -> Vec<BugfixMatch> {
+  let mut visitor = BugfixVisitor::default();
+  program.visit_with(&mut visitor);
+  visitor.matches
+}
+
+struct Scope {
+  bindings: HashSet<String>,
+  // function or program scope, the block shadowing bug stops there
+  function: bool,
+}
+
+#[derive(Default)]
+struct BugfixVisitor {
+  matches: Vec<BugfixMatch>,
+  scopes: Vec<Scope>,
+}
+
+impl BugfixVisitor {
+  fn add(&mut self, feature: BugfixFeature, span: Span) {
+    self.matches.push(BugfixMatch { feature, span });
+  }
+
+  fn is_bound(&self, name: &str) -> bool {
+    self
+      .scopes
+      .iter()
+      .any(|scope| scope.bindings.contains(name))
+  }
+
+  fn with_scope(&mut self, bindings: HashSet<String>, function: bool, op: impl FnOnce(&mut Self)) {
+    self.scopes.push(Scope { bindings, function });
+    op(self);
+    self.scopes.pop();
+  }
+
+  fn visit_function_like(
+    &mut self,
+    params: &[&Pat],
+    body: Option<&[Stmt]>,
+    op: impl FnOnce(&mut Self),
+  ) {
+    for param in params {
+      if let Pat::Assign(AssignPat { span, left, .. }) = param {
+        if let Pat::Ident(ident) = &**left {
+          if self.is_bound(&ident.sym) {
+            self.add(BugfixFeature::EdgeDefaultParameters, *span);
+          }
+        }
+      }
+    }
+    let mut bindings = HashSet::new();
+    for param in params {
+      bindings.extend(
+        pat_ids(param)
+          .into_iter()
+          .map(|ident| ident.sym.to_string()),
+      );
+    }
+    if let Some(stmts) = body {
+      bindings.extend(var_names(stmts));
+      bindings.extend(lexical_names(stmts.iter()));
+    }
+    self.with_scope(bindings, true, op);
+  }
+}
+
+impl Visit for BugfixVisitor {
+  noop_visit_type!();
+
+  fn visit_module(&mut self, n: &Module) {
+    let mut bindings = var_names(&n.body[..]);
+    for item in &n.body {
+      match item {
+        ModuleItem::Stmt(stmt) => bindings.extend(lexical_names(std::iter::once(stmt))),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+          bindings.extend(decl_names(decl, false));
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+          bindings.extend(import.specifiers.iter().map(|specifier| match specifier {
+            ImportSpecifier::Named(s) => s.local.sym.to_string(),
+            ImportSpecifier::Default(s) => s.local.sym.to_string(),
+            ImportSpecifier::Namespace(s) => s.local.sym.to_string(),
+          }));
+        }
+        _ => (),
+      }
+    }
+    self.with_scope(bindings, true, |v| n.visit_children_with(v));
+  }
+
+  fn visit_script(&mut self, n: &Script) {
+    let mut bindings = var_names(&n.body[..]);
+    bindings.extend(lexical_names(n.body.iter()));
+    self.with_scope(bindings, true, |v| n.visit_children_with(v));
+  }
+
+  fn visit_function(&mut self, n: &Function) {
+    let params: Vec<&Pat> = n.params.iter().map(|param| &param.pat).collect();
+    let body = n.body.as_ref().map(|body| &body.stmts[..]);
+    self.visit_function_like(&params, body, |v| {
+      n.decorators.visit_with(v);
+      n.params.visit_with(v);
+      // the body shares the scope of the parameters
+      if let Some(body) = &n.body {
+        body.stmts.visit_with(v);
+      }
+    });
+  }
+
+  fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+    let params: Vec<&Pat> = n.params.iter().collect();
+    let body = match &*n.body {
+      BlockStmtOrExpr::BlockStmt(body) => Some(&body.stmts[..]),
+      BlockStmtOrExpr::Expr(..) => None,
+    };
+    self.visit_function_like(&params, body, |v| {
+      n.params.visit_with(v);
+      match &*n.body {
+        BlockStmtOrExpr::BlockStmt(body) => body.stmts.visit_with(v),
+        BlockStmtOrExpr::Expr(expr) => expr.visit_with(v),
+      }
+    });
+  }
+
+  fn visit_block_stmt(&mut self, n: &BlockStmt) {
+    let bindings = lexical_names(n.stmts.iter());
+    self.with_scope(bindings, false, |v| n.visit_children_with(v));
+  }
+
+  fn visit_for_stmt(&mut self, n: &ForStmt) {
+    let bindings = match &n.init {
+      Some(VarDeclOrExpr::VarDecl(decl)) if decl.kind != VarDeclKind::Var => var_decl_names(decl),
+      _ => HashSet::new(),
+    };
+    self.with_scope(bindings, false, |v| n.visit_children_with(v));
+  }
+
+  fn visit_for_in_stmt(&mut self, n: &ForInStmt) {
+    let bindings = for_head_names(&n.left);
+    self.with_scope(bindings, false, |v| n.visit_children_with(v));
+  }
+
+  fn visit_for_of_stmt(&mut self, n: &ForOfStmt) {
+    let bindings = for_head_names(&n.left);
+    self.with_scope(bindings, false, |v| n.visit_children_with(v));
+  }
+
+  fn visit_catch_clause(&mut self, n: &CatchClause) {
+    let bindings = n
+      .param
+      .iter()
+      .flat_map(pat_ids)
+      .map(|ident| ident.sym.to_string())
+      .collect();
+    self.with_scope(bindings, false, |v| n.visit_children_with(v));
+  }
+
+  // { let a } shadowing `a` of an outer scope in the same function
+  fn visit_var_decl(&mut self, n: &VarDecl) {
+    n.visit_children_with(self);
+    if n.kind == VarDeclKind::Var {
+      return;
+    }
+    let Some((current, outer)) = self.scopes.split_last() else {
+      return;
+    };
+    if current.function {
+      return;
+    }
+    let mut shadowing = vec![];
+    for ident in n.decls.iter().flat_map(|decl| pat_ids(&decl.name)) {
+      for scope in outer.iter().rev() {
+        if scope.bindings.contains(&*ident.sym) {
+          shadowing.push(ident.span);
+          break;
+        }
+        if scope.function {
+          break;
+        }
+      }
+    }
+    for span in shadowing {
+      self.add(BugfixFeature::SafariBlockShadowing, span);
+    }
+  }
+
+  // (function a({ a }) {})
+  fn visit_fn_expr(&mut self, n: &FnExpr) {
+    n.visit_children_with(self);
+    let Some(name) = &n.ident else {
+      return;
+    };
+    for param in &n.function.params {
+      if matches!(param.pat, Pat::Ident(..)) {
+        continue;
+      }
+      if let Some(ident) = pat_ids(&param.pat)
+        .into_iter()
+        .find(|ident| ident.sym == name.sym)
+      {
+        self.add(BugfixFeature::SafariIdDestructuringCollision, ident.span);
+      }
+    }
+  }
+
+  // tag`a`
+  fn visit_tagged_tpl(&mut self, n: &TaggedTpl) {
+    n.visit_children_with(self);
+    self.add(BugfixFeature::TaggedTemplateCaching, n.span);
+  }
+
+  // a?.(...b) | a?.b(...c)
+  fn visit_opt_call(&mut self, n: &OptCall) {
+    n.visit_children_with(self);
+    if n.args.iter().any(|arg| arg.spread.is_some()) {
+      self.add(BugfixFeature::V8SpreadParametersInOptionalChaining, n.span);
+    }
+  }
+}
+
+// Names bound by a pattern, default values are skipped
+fn pat_ids(pat: &Pat) -> Vec<Ident> {
+  let mut ids = vec![];
+  collect_pat_ids(pat, &mut ids);
+  ids
+}
+
+fn collect_pat_ids(pat: &Pat, ids: &mut Vec<Ident>) {
+  match pat {
+    Pat::Ident(ident) => ids.push(ident.id.clone()),
+    Pat::Array(array) => {
+      for elem in array.elems.iter().flatten() {
+        collect_pat_ids(elem, ids);
+      }
+    }
+    Pat::Rest(rest) => collect_pat_ids(&rest.arg, ids),
+    Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ObjectPatProp::KeyValue(kv) => collect_pat_ids(&kv.value, ids),
+          ObjectPatProp::Assign(assign) => ids.push(assign.key.clone()),
+          ObjectPatProp::Rest(rest) => collect_pat_ids(&rest.arg, ids),
+        }
+      }
+    }
+    Pat::Assign(assign) => collect_pat_ids(&assign.left, ids),
+    Pat::Invalid(..) | Pat::Expr(..) => (),
+  }
+}
+
+fn var_decl_names(decl: &VarDecl) -> HashSet<String> {
+  decl
+    .decls
+    .iter()
+    .flat_map(|decl| pat_ids(&decl.name))
+    .map(|ident| ident.sym.to_string())
+    .collect()
+}
+
+fn for_head_names(head: &ForHead) -> HashSet<String> {
+  match head {
+    ForHead::VarDecl(decl) if decl.kind != VarDeclKind::Var => var_decl_names(decl),
+    _ => HashSet::new(),
+  }
+}
+
+fn decl_names(decl: &Decl, lexical_only: bool) -> HashSet<String> {
+  match decl {
+    Decl::Class(class) => HashSet::from([class.ident.sym.to_string()]),
+    Decl::Fn(function) => HashSet::from([function.ident.sym.to_string()]),
+    Decl::Var(var) if !lexical_only || var.kind != VarDeclKind::Var => var_decl_names(var),
+    _ => HashSet::new(),
+  }
+}
+
+// let, const, class and function declarations directly in `stmts`
+fn lexical_names<'a>(stmts: impl Iterator<Item = &'a Stmt>) -> HashSet<String> {
+  stmts
+    .filter_map(|stmt| match stmt {
+      Stmt::Decl(decl) => Some(decl_names(decl, true)),
+      _ => None,
+    })
+    .flatten()
+    .collect()
+}
+
+// `var` declarations hoisted to the function or program scope of `stmts`
+fn var_names<N>(stmts: &N) -> HashSet<String>
+where
+  N: VisitWith<VarCollector> + ?Sized,
+{
+  let mut collector = VarCollector::default();
+  stmts.visit_with(&mut collector);
+  collector.names
+}
+
+#[derive(Default)]
+struct VarCollector {
+  names: HashSet<String>,
+}
+
+impl Visit for VarCollector {
+  noop_visit_type!();
+
+  fn visit_var_decl(&mut self, n: &VarDecl) {
+    if n.kind == VarDeclKind::Var {
+      self.names.extend(var_decl_names(n));
+    }
+  }
+
+  // nested functions hoist to their own scope
+  fn visit_function(&mut self, _n: &Function) {}
+
+  fn visit_arrow_expr(&mut self, _n: &ArrowExpr) {}
+}
@@ -0,0 +1,675 @@
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use swc_core::common::comments::SingleThreadedComments;
+use swc_core::common::{Mark, Span, SyntaxContext, DUMMY_SP, GLOBALS};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::transforms::base::{feature::FeatureFlag, Assumptions};
+use swc_core::ecma::visit::{noop_visit_type, Fold, Visit, VisitWith};
+use swc_ecma_preset_env::{preset_env, Config, Mode, Targets, Version, Versions};
+
+// How a built-in shows up in code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Usage {
+  // structuredClone(a)
+  Global(&'static str),
+  // Object.hasOwn(a, b)
+  Static(&'static str, &'static str),
+  // a.at(-1)
+  Instance(&'static str),
+}
+
+// (API, usage, core-js module)
+const BUILTINS: &[(&str, Usage, &str)] = &[
+  // globals
+  ("Promise", Usage::Global("Promise"), "es.promise"),
+  ("Map", Usage::Global("Map"), "es.map"),
+  ("Set", Usage::Global("Set"), "es.set"),
+  ("WeakMap", Usage::Global("WeakMap"), "es.weak-map"),
+  ("WeakSet", Usage::Global("WeakSet"), "es.weak-set"),
+  ("Symbol", Usage::Global("Symbol"), "es.symbol"),
+  ("globalThis", Usage::Global("globalThis"), "es.global-this"),
+  (
+    "AggregateError",
+    Usage::Global("AggregateError"),
+    "es.aggregate-error",
+  ),
+  (
+    "ArrayBuffer",
+    Usage::Global("ArrayBuffer"),
+    "es.array-buffer.constructor",
+  ),
+  ("DataView", Usage::Global("DataView"), "es.data-view"),
+  (
+    "Int8Array",
+    Usage::Global("Int8Array"),
+    "es.typed-array.int8-array",
+  ),
+  (
+    "Uint8Array",
+    Usage::Global("Uint8Array"),
+    "es.typed-array.uint8-array",
+  ),
+  (
+    "Uint8ClampedArray",
+    Usage::Global("Uint8ClampedArray"),
+    "es.typed-array.uint8-clamped-array",
+  ),
+  (
+    "Int16Array",
+    Usage::Global("Int16Array"),
+    "es.typed-array.int16-array",
+  ),
+  (
+    "Uint16Array",
+    Usage::Global("Uint16Array"),
+    "es.typed-array.uint16-array",
+  ),
+  (
+    "Int32Array",
+    Usage::Global("Int32Array"),
+    "es.typed-array.int32-array",
+  ),
+  (
+    "Uint32Array",
+    Usage::Global("Uint32Array"),
+    "es.typed-array.uint32-array",
+  ),
+  (
+    "Float32Array",
+    Usage::Global("Float32Array"),
+    "es.typed-array.float32-array",
+  ),
+  (
+    "Float64Array",
+    Usage::Global("Float64Array"),
+    "es.typed-array.float64-array",
+  ),
+  (
+    "structuredClone",
+    Usage::Global("structuredClone"),
+    "web.structured-clone",
+  ),
+  // Object
+  (
+    "Object.assign",
+    Usage::Static("Object", "assign"),
+    "es.object.assign",
+  ),
+  (
+    "Object.entries",
+    Usage::Static("Object", "entries"),
+    "es.object.entries",
+  ),
+  (
+    "Object.values",
+    Usage::Static("Object", "values"),
+    "es.object.values",
+  ),
+  (
+    "Object.fromEntries",
+    Usage::Static("Object", "fromEntries"),
+    "es.object.from-entries",
+  ),
+  (
+    "Object.getOwnPropertyDescriptors",
+    Usage::Static("Object", "getOwnPropertyDescriptors"),
+    "es.object.get-own-property-descriptors",
+  ),
+  (
+    "Object.hasOwn",
+    Usage::Static("Object", "hasOwn"),
+    "es.object.has-own",
+  ),
+  ("Object.is", Usage::Static("Object", "is"), "es.object.is"),
+  (
+    "Object.setPrototypeOf",
+    Usage::Static("Object", "setPrototypeOf"),
+    "es.object.set-prototype-of",
+  ),
+  // Array
+  (
+    "Array.from",
+    Usage::Static("Array", "from"),
+    "es.array.from",
+  ),
+  ("Array.of", Usage::Static("Array", "of"), "es.array.of"),
+  ("Array.prototype.at", Usage::Instance("at"), "es.array.at"),
+  (
+    "Array.prototype.copyWithin",
+    Usage::Instance("copyWithin"),
+    "es.array.copy-within",
+  ),
+  (
+    "Array.prototype.fill",
+    Usage::Instance("fill"),
+    "es.array.fill",
+  ),
+  (
+    "Array.prototype.find",
+    Usage::Instance("find"),
+    "es.array.find",
+  ),
+  (
+    "Array.prototype.findIndex",
+    Usage::Instance("findIndex"),
+    "es.array.find-index",
+  ),
+  (
+    "Array.prototype.findLast",
+    Usage::Instance("findLast"),
+    "es.array.find-last",
+  ),
+  (
+    "Array.prototype.findLastIndex",
+    Usage::Instance("findLastIndex"),
+    "es.array.find-last-index",
+  ),
+  (
+    "Array.prototype.flat",
+    Usage::Instance("flat"),
+    "es.array.flat",
+  ),
+  (
+    "Array.prototype.flatMap",
+    Usage::Instance("flatMap"),
+    "es.array.flat-map",
+  ),
+  (
+    "Array.prototype.includes",
+    Usage::Instance("includes"),
+    "es.array.includes",
+  ),
+  (
+    "Array.prototype.toReversed",
+    Usage::Instance("toReversed"),
+    "es.array.to-reversed",
+  ),
+  (
+    "Array.prototype.toSorted",
+    Usage::Instance("toSorted"),
+    "es.array.to-sorted",
+  ),
+  (
+    "Array.prototype.toSpliced",
+    Usage::Instance("toSpliced"),
+    "es.array.to-spliced",
+  ),
+  (
+    "Array.prototype.with",
+    Usage::Instance("with"),
+    "es.array.with",
+  ),
+  // String
+  (
+    "String.fromCodePoint",
+    Usage::Static("String", "fromCodePoint"),
+    "es.string.from-code-point",
+  ),
+  (
+    "String.raw",
+    Usage::Static("String", "raw"),
+    "es.string.raw",
+  ),
+  (
+    "String.prototype.at",
+    Usage::Instance("at"),
+    "es.string.at-alternative",
+  ),
+  (
+    "String.prototype.codePointAt",
+    Usage::Instance("codePointAt"),
+    "es.string.code-point-at",
+  ),
+  (
+    "String.prototype.endsWith",
+    Usage::Instance("endsWith"),
+    "es.string.ends-with",
+  ),
+  (
+    "String.prototype.includes",
+    Usage::Instance("includes"),
+    "es.string.includes",
+  ),
+  (
+    "String.prototype.isWellFormed",
+    Usage::Instance("isWellFormed"),
+    "es.string.is-well-formed",
+  ),
+  (
+    "String.prototype.matchAll",
+    Usage::Instance("matchAll"),
+    "es.string.match-all",
+  ),
+  (
+    "String.prototype.padEnd",
+    Usage::Instance("padEnd"),
+    "es.string.pad-end",
+  ),
+  (
+    "String.prototype.padStart",
+    Usage::Instance("padStart"),
+    "es.string.pad-start",
+  ),
+  (
+    "String.prototype.repeat",
+    Usage::Instance("repeat"),
+    "es.string.repeat",
+  ),
+  (
+    "String.prototype.replaceAll",
+    Usage::Instance("replaceAll"),
+    "es.string.replace-all",
+  ),
+  (
+    "String.prototype.startsWith",
+    Usage::Instance("startsWith"),
+    "es.string.starts-with",
+  ),
+  (
+    "String.prototype.toWellFormed",
+    Usage::Instance("toWellFormed"),
+    "es.string.to-well-formed",
+  ),
+  (
+    "String.prototype.trimEnd",
+    Usage::Instance("trimEnd"),
+    "es.string.trim-end",
+  ),
+  (
+    "String.prototype.trimStart",
+    Usage::Instance("trimStart"),
+    "es.string.trim-start",
+  ),
+  // Promise
+  (
+    "Promise.allSettled",
+    Usage::Static("Promise", "allSettled"),
+    "es.promise.all-settled",
+  ),
+  (
+    "Promise.any",
+    Usage::Static("Promise", "any"),
+    "es.promise.any",
+  ),
+  (
+    "Promise.prototype.finally",
+    Usage::Instance("finally"),
+    "es.promise.finally",
+  ),
+  // Number
+  (
+    "Number.EPSILON",
+    Usage::Static("Number", "EPSILON"),
+    "es.number.epsilon",
+  ),
+  (
+    "Number.isFinite",
+    Usage::Static("Number", "isFinite"),
+    "es.number.is-finite",
+  ),
+  (
+    "Number.isInteger",
+    Usage::Static("Number", "isInteger"),
+    "es.number.is-integer",
+  ),
+  (
+    "Number.isNaN",
+    Usage::Static("Number", "isNaN"),
+    "es.number.is-nan",
+  ),
+  (
+    "Number.isSafeInteger",
+    Usage::Static("Number", "isSafeInteger"),
+    "es.number.is-safe-integer",
+  ),
+  (
+    "Number.MAX_SAFE_INTEGER",
+    Usage::Static("Number", "MAX_SAFE_INTEGER"),
+    "es.number.max-safe-integer",
+  ),
+  (
+    "Number.MIN_SAFE_INTEGER",
+    Usage::Static("Number", "MIN_SAFE_INTEGER"),
+    "es.number.min-safe-integer",
+  ),
+  (
+    "Number.parseFloat",
+    Usage::Static("Number", "parseFloat"),
+    "es.number.parse-float",
+  ),
+  (
+    "Number.parseInt",
+    Usage::Static("Number", "parseInt"),
+    "es.number.parse-int",
+  ),
+  // Math
+  (
+    "Math.acosh",
+    Usage::Static("Math", "acosh"),
+    "es.math.acosh",
+  ),
+  (
+    "Math.asinh",
+    Usage::Static("Math", "asinh"),
+    "es.math.asinh",
+  ),
+  (
+    "Math.atanh",
+    Usage::Static("Math", "atanh"),
+    "es.math.atanh",
+  ),
+  ("Math.cbrt", Usage::Static("Math", "cbrt"), "es.math.cbrt"),
+  (
+    "Math.clz32",
+    Usage::Static("Math", "clz32"),
+    "es.math.clz32",
+  ),
+  ("Math.cosh", Usage::Static("Math", "cosh"), "es.math.cosh"),
+  (
+    "Math.expm1",
+    Usage::Static("Math", "expm1"),
+    "es.math.expm1",
+  ),
+  (
+    "Math.fround",
+    Usage::Static("Math", "fround"),
+    "es.math.fround",
+  ),
+  (
+    "Math.hypot",
+    Usage::Static("Math", "hypot"),
+    "es.math.hypot",
+  ),
+  ("Math.imul", Usage::Static("Math", "imul"), "es.math.imul"),
+  (
+    "Math.log10",
+    Usage::Static("Math", "log10"),
+    "es.math.log10",
+  ),
+  (
+    "Math.log1p",
+    Usage::Static("Math", "log1p"),
+    "es.math.log1p",
+  ),
+  ("Math.log2", Usage::Static("Math", "log2"), "es.math.log2"),
+  ("Math.sign", Usage::Static("Math", "sign"), "es.math.sign"),
+  ("Math.sinh", Usage::Static("Math", "sinh"), "es.math.sinh"),
+  ("Math.tanh", Usage::Static("Math", "tanh"), "es.math.tanh"),
+  (
+    "Math.trunc",
+    Usage::Static("Math", "trunc"),
+    "es.math.trunc",
+  ),
+  // Symbol
+  (
+    "Symbol.asyncIterator",
+    Usage::Static("Symbol", "asyncIterator"),
+    "es.symbol.async-iterator",
+  ),
+  (
+    "Symbol.prototype.description",
+    Usage::Instance("description"),
+    "es.symbol.description",
+  ),
+  // Reflect
+  (
+    "Reflect.apply",
+    Usage::Static("Reflect", "apply"),
+    "es.reflect.apply",
+  ),
+  (
+    "Reflect.construct",
+    Usage::Static("Reflect", "construct"),
+    "es.reflect.construct",
+  ),
+  (
+    "Reflect.defineProperty",
+    Usage::Static("Reflect", "defineProperty"),
+    "es.reflect.define-property",
+  ),
+  (
+    "Reflect.deleteProperty",
+    Usage::Static("Reflect", "deleteProperty"),
+    "es.reflect.delete-property",
+  ),
+  (
+    "Reflect.get",
+    Usage::Static("Reflect", "get"),
+    "es.reflect.get",
+  ),
+  (
+    "Reflect.getOwnPropertyDescriptor",
+    Usage::Static("Reflect", "getOwnPropertyDescriptor"),
+    "es.reflect.get-own-property-descriptor",
+  ),
+  (
+    "Reflect.getPrototypeOf",
+    Usage::Static("Reflect", "getPrototypeOf"),
+    "es.reflect.get-prototype-of",
+  ),
+  (
+    "Reflect.has",
+    Usage::Static("Reflect", "has"),
+    "es.reflect.has",
+  ),
+  (
+    "Reflect.isExtensible",
+    Usage::Static("Reflect", "isExtensible"),
+    "es.reflect.is-extensible",
+  ),
+  (
+    "Reflect.ownKeys",
+    Usage::Static("Reflect", "ownKeys"),
+    "es.reflect.own-keys",
+  ),
+  (
+    "Reflect.preventExtensions",
+    Usage::Static("Reflect", "preventExtensions"),
+    "es.reflect.prevent-extensions",
+  ),
+  (
+    "Reflect.set",
+    Usage::Static("Reflect", "set"),
+    "es.reflect.set",
+  ),
+  (
+    "Reflect.setPrototypeOf",
+    Usage::Static("Reflect", "setPrototypeOf"),
+    "es.reflect.set-prototype-of",
+  ),
+];
+
+// Objects a global can be read from, e.g. window.structuredClone
+const GLOBAL_OBJECTS: &[&str] = &["global", "globalThis", "self", "window"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinMatch {
+  // e.g. Array.prototype.at
+  pub name: &'static str,
+  // core-js module polyfilling it, e.g. es.array.at
+  pub module: &'static str,
+  pub span: Span,
+  // the global it's read from is a local binding, e.g. a bundled Promise helper
+  pub shadowed: bool,
+  // matched by the method name alone, e.g. `a.at(-1)` with `a` maybe not an array
+  pub low_confidence: bool,
+}
+
+// core-js modules `targets` need, resolved by expanding `import "core-js/stable"` the same way
+// preset-env does in entry mode. Cached per targets, most callers check every file against the
+// same ones
+pub fn required_modules(targets: Versions) -> HashSet<String> {
+  // `Versions` isn't `Hash`, there are only a few distinct targets per process anyway
+  static CACHE: Mutex<Vec<(Versions, HashSet<String>)>> = Mutex::new(Vec::new());
+  let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+  if let Some((_, modules)) = cache.iter().find(|(cached, _)| *cached == targets) {
+    return modules.clone();
+  }
+  let modules = expand_core_js_entry(targets);
+  cache.push((targets, modules.clone()));
+  modules
+}
+
+fn expand_core_js_entry(targets: Versions) -> HashSet<String> {
+  let entry = Module {
+    span: DUMMY_SP,
+    body: vec![ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+      span: DUMMY_SP,
+      specifiers: vec![],
+      src: Box::new(Str::from("core-js/stable")),
+      type_only: false,
+      with: None,
+      phase: Default::default(),
+    }))],
+    shebang: None,
+  };
+  let mut pass = preset_env(
+    Mark::new(),
+    None::<SingleThreadedComments>,
+    Config {
+      targets: Some(Targets::Versions(targets)),
+      mode: Some(Mode::Entry),
+      core_js: Some(Version {
+        major: 3,
+        minor: 33,
+        patch: 0,
+      }),
+      ..Default::default()
+    },
+    Assumptions::default(),
+    &mut FeatureFlag::default(),
+  );
+  pass
+    .fold_module(entry)
+    .body
+    .iter()
+    .filter_map(|item| match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl { src, .. })) => src
+        .value
+        .strip_prefix("core-js/modules/")
+        .and_then(|module| module.strip_suffix(".js"))
+        .map(String::from),
+      _ => None,
+    })
+    .collect()
+}
+
+// Every core-js module preset-env polyfills, what `include` and `exclude` accept besides features
+pub fn core_js_modules() -> &'static HashSet<String> {
+  static MODULES: OnceLock<HashSet<String>> = OnceLock::new();
+  MODULES.get_or_init(|| GLOBALS.set(&Default::default(), || required_modules(Default::default())))
+}
+
+// Every built-in used in `program`, whether or not the targets support it
+// `program` went through the resolver with `unresolved_mark`
+pub fn analyze_builtins(program: &Program, unresolved_mark: Mark) -> Vec<BuiltinMatch> {
+  let mut visitor = BuiltinVisitor {
+    matches: vec![],
+    unresolved: SyntaxContext::empty().apply_mark(unresolved_mark),
+  };
+  program.visit_with(&mut visitor);
+  visitor.matches
+}
+
+struct BuiltinVisitor {
+  matches: Vec<BuiltinMatch>,
+  unresolved: SyntaxContext,
+}
+
+impl BuiltinVisitor {
+  fn add(&mut self, span: Span, global: &Ident, filter: impl Fn(&Usage) -> bool) {
+    let shadowed = global.span.ctxt != self.unresolved;
+    for (name, usage, module) in BUILTINS {
+      if filter(usage) {
+        self.matches.push(BuiltinMatch {
+          name,
+          module,
+          span,
+          shadowed,
+          low_confidence: false,
+        });
+      }
+    }
+  }
+}
+
+impl Visit for BuiltinVisitor {
+  noop_visit_type!();
+
+  // structuredClone(a)
+  fn visit_expr(&mut self, n: &Expr) {
+    if let Expr::Ident(ident) = n {
+      self.add(
+        ident.span,
+        ident,
+        |usage| matches!(usage, Usage::Global(g) if *g == &*ident.sym),
+      );
+    }
+    n.visit_children_with(self);
+  }
+
+  // Object.hasOwn(a, b) | window.structuredClone(a) | a.at(-1)
+  fn visit_member_expr(&mut self, n: &MemberExpr) {
+    n.visit_children_with(self);
+    let prop = match &n.prop {
+      MemberProp::Ident(ident) => ident.sym.clone(),
+      MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+        Expr::Lit(Lit::Str(s)) => s.value.clone(),
+        _ => return,
+      },
+      MemberProp::PrivateName(..) => return,
+    };
+    if let Expr::Ident(obj) = &*n.obj {
+      if GLOBAL_OBJECTS.contains(&&*obj.sym) {
+        self.add(
+          n.span,
+          obj,
+          |usage| matches!(usage, Usage::Global(g) if *g == &*prop),
+        );
+        return;
+      }
+      if BUILTINS
+        .iter()
+        .any(|(_, usage, _)| matches!(usage, Usage::Static(o, _) if *o == &*obj.sym))
+      {
+        self.add(
+          n.span,
+          obj,
+          |usage| matches!(usage, Usage::Static(o, p) if *o == &*obj.sym && *p == &*prop),
+        );
+        return;
+      }
+    }
+    // narrow down by the receiver when it's a literal or a prototype, e.g. [1, 2].at(-1) or
+    // Array.prototype.at
+    let receiver = match n.obj.unwrap_parens() {
+      Expr::Array(..) => Some(String::from("Array.prototype.")),
+      Expr::Lit(Lit::Str(..)) | Expr::Tpl(..) => Some(String::from("String.prototype.")),
+      Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prototype),
+        ..
+      }) if &*prototype.sym == "prototype" => match &**obj {
+        Expr::Ident(obj) => Some(format!("{}.prototype.", obj.sym)),
+        _ => None,
+      },
+      _ => None,
+    };
+    for (name, usage, module) in BUILTINS {
+      if matches!(usage, Usage::Instance(p) if *p == &*prop)
+        && receiver
+          .as_ref()
+          .map_or(true, |r| name.starts_with(r.as_str()))
+      {
+        self.matches.push(BuiltinMatch {
+          name,
+          module,
+          span: n.span,
+          shadowed: false,
+          low_confidence: receiver.is_none(),
+        });
+      }
+    }
+  }
+}
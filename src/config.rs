@@ -0,0 +1,232 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+use serde_json::Value;
+
+// Config files browserslist looks for in each directory, from `path` up to the root
+const CONFIG_FILES: &[&str] = &["browserslist", ".browserslistrc", "package.json"];
+
+// Queries browserslist would pick for `path`, a file or a directory relative to the current one,
+// in order: BROWSERSLIST, BROWSERSLIST_CONFIG, then the closest browserslist, .browserslistrc or
+// package.json `browserslist`. `env` picks a section like [production] or [legacy], falling back
+// to BROWSERSLIST_ENV, NODE_ENV and "production". `defaults` if nothing is found
+pub fn load_browserslist(path: &str, env: Option<&str>) -> Result<Vec<String>, anyhow::Error> {
+  if let Ok(query) = env::var("BROWSERSLIST") {
+    return Ok(vec![query]);
+  }
+  let env_name = env
+    .map(String::from)
+    .or_else(|| env::var("BROWSERSLIST_ENV").ok())
+    .or_else(|| env::var("NODE_ENV").ok())
+    .unwrap_or_else(|| String::from("production"));
+  let queries = match env::var("BROWSERSLIST_CONFIG") {
+    Ok(config) => read_config(Path::new(&config), &env_name)?
+      .ok_or_else(|| anyhow!("no `browserslist` field in {}", config))?,
+    Err(..) => {
+      // absolute, to look up past the current directory
+      let path = env::current_dir()
+        .context("failed to read the current directory")?
+        .join(path);
+      find_config(&path, &env_name)?.unwrap_or_default()
+    }
+  };
+  if queries.is_empty() {
+    return Ok(vec![String::from("defaults")]);
+  }
+  Ok(queries)
+}
+
+// Queries of the closest config to `path`. Two configs in one directory are an error, like in
+// browserslist
+fn find_config(path: &Path, env: &str) -> Result<Option<Vec<String>>, anyhow::Error> {
+  for dir in path.ancestors() {
+    let mut found = vec![];
+    for file in CONFIG_FILES {
+      let file = dir.join(file);
+      if !file.is_file() {
+        continue;
+      }
+      // a package.json without `browserslist` doesn't count
+      if let Some(queries) = read_config(&file, env)? {
+        found.push((file, queries));
+      }
+    }
+    match &found[..] {
+      [] => continue,
+      [(_, queries)] => return Ok(Some(queries.clone())),
+      [(first, _), (second, ..), ..] => bail!(
+        "{} and {} both configure browserslist in {}",
+        first.display(),
+        second.display(),
+        dir.display()
+      ),
+    }
+  }
+  Ok(None)
+}
+
+fn read_config(file: &Path, env: &str) -> Result<Option<Vec<String>>, anyhow::Error> {
+  let content =
+    fs::read_to_string(file).with_context(|| format!("failed to read {}", file.display()))?;
+  if file.file_name().is_some_and(|name| name == "package.json") {
+    let package: Value = serde_json::from_str(&content)
+      .with_context(|| format!("failed to parse {}", file.display()))?;
+    return package_queries(&package["browserslist"], env)
+      .with_context(|| format!("invalid `browserslist` in {}", file.display()));
+  }
+  Ok(Some(rc_queries(&content, env)))
+}
+
+// "last 2 versions" | ["chrome 87", "safari 13.1"] | { "production": [...], "legacy": [...] }
+fn package_queries(config: &Value, env: &str) -> Result<Option<Vec<String>>, anyhow::Error> {
+  let strings = |value: &Value| -> Result<Vec<String>, anyhow::Error> {
+    match value {
+      Value::String(query) => Ok(vec![query.clone()]),
+      Value::Array(queries) => queries
+        .iter()
+        .map(|query| {
+          query
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| anyhow!("expected a string, found {}", query))
+        })
+        .collect(),
+      _ => bail!("expected a string or an array, found {}", value),
+    }
+  };
+  match config {
+    Value::Null => Ok(None),
+    Value::Object(envs) => match envs.get(env).or_else(|| envs.get("defaults")) {
+      Some(queries) => strings(queries).map(Some),
+      None => Ok(Some(vec![])),
+    },
+    _ => strings(config).map(Some),
+  }
+}
+
+// Lines of a browserslist or .browserslistrc file, `[production staging]` starts the queries of
+// those environments, lines before any section are the defaults
+fn rc_queries(content: &str, env: &str) -> Vec<String> {
+  let mut defaults = vec![];
+  let mut env_queries: Option<Vec<String>> = None;
+  let mut section: Option<bool> = None;
+  for line in content.lines() {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    if line.is_empty() {
+      continue;
+    }
+    if let Some(names) = line
+      .strip_prefix('[')
+      .and_then(|line| line.strip_suffix(']'))
+    {
+      let matched = names.split_whitespace().any(|name| name == env);
+      if matched {
+        env_queries.get_or_insert_with(Vec::new);
+      }
+      section = Some(matched);
+      continue;
+    }
+    match section {
+      None => defaults.push(line.to_string()),
+      Some(true) => env_queries
+        .get_or_insert_with(Vec::new)
+        .push(line.to_string()),
+      Some(false) => (),
+    }
+  }
+  env_queries.unwrap_or(defaults)
+}
+
+// `compilerOptions.target` of a tsconfig.json, or of the configs it `extends`
+pub fn load_tsconfig_target(tsconfig: &Path) -> Result<Option<String>, anyhow::Error> {
+  let content = fs::read_to_string(tsconfig)
+    .with_context(|| format!("failed to read {}", tsconfig.display()))?;
+  let config: Value = serde_json::from_str(&strip_jsonc(&content))
+    .with_context(|| format!("failed to parse {}", tsconfig.display()))?;
+  if let Some(target) = config["compilerOptions"]["target"].as_str() {
+    return Ok(Some(target.to_string()));
+  }
+  let extends = match &config["extends"] {
+    Value::String(base) => vec![base.as_str()],
+    Value::Array(bases) => bases.iter().filter_map(Value::as_str).collect(),
+    _ => vec![],
+  };
+  // later configs override earlier ones
+  for base in extends.into_iter().rev() {
+    if let Some(target) = load_tsconfig_target(&resolve_extends(tsconfig, base)?)? {
+      return Ok(Some(target));
+    }
+  }
+  Ok(None)
+}
+
+// "./base.json" | "./base" | "@tsconfig/node18/tsconfig.json" | "@tsconfig/strictest"
+fn resolve_extends(tsconfig: &Path, base: &str) -> Result<PathBuf, anyhow::Error> {
+  let dir = tsconfig.parent().unwrap_or(Path::new("."));
+  let roots: Vec<PathBuf> = if base.starts_with('.') || Path::new(base).is_absolute() {
+    vec![dir.join(base)]
+  } else {
+    dir
+      .ancestors()
+      .map(|dir| dir.join("node_modules").join(base))
+      .collect()
+  };
+  roots
+    .into_iter()
+    .flat_map(|root| {
+      [
+        root.clone(),
+        root.with_file_name(format!(
+          "{}.json",
+          root.file_name().unwrap_or_default().to_string_lossy()
+        )),
+        root.join("tsconfig.json"),
+      ]
+    })
+    .find(|path| path.is_file())
+    .ok_or_else(|| anyhow!("can't find {} extended by {}", base, tsconfig.display()))
+}
+
+// tsconfig.json allows comments and trailing commas
+fn strip_jsonc(content: &str) -> String {
+  let mut json = String::with_capacity(content.len());
+  let mut chars = content.chars().peekable();
+  let mut in_string = false;
+  while let Some(c) = chars.next() {
+    if in_string {
+      json.push(c);
+      match c {
+        '\\' => json.extend(chars.next()),
+        '"' => in_string = false,
+        _ => (),
+      }
+      continue;
+    }
+    match (c, chars.peek()) {
+      ('"', _) => {
+        in_string = true;
+        json.push(c);
+      }
+      ('/', Some('/')) => while chars.next_if(|c| *c != '\n').is_some() {},
+      ('/', Some('*')) => {
+        chars.next();
+        while let Some(c) = chars.next() {
+          if c == '*' && chars.next_if_eq(&'/').is_some() {
+            break;
+          }
+        }
+      }
+      ('}' | ']', _) => {
+        let end = json.trim_end().len();
+        if json[..end].ends_with(',') {
+          json.truncate(end - 1);
+        }
+        json.push(c);
+      }
+      _ => json.push(c),
+    }
+  }
+  json
+}
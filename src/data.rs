@@ -0,0 +1,220 @@
+use preset_env_base::version::Version;
+use preset_env_base::{BrowserData, Versions};
+
+// Features without a preset-env plugin, versions from https://github.com/mdn/browser-compat-data
+
+fn version(v: &str) -> Option<Version> {
+  v.parse().ok()
+}
+
+// (?<=a) (?<!a)
+pub fn lookbehind_data() -> Versions {
+  BrowserData {
+    chrome: version("62"),
+    edge: version("79"),
+    firefox: version("78"),
+    opera: version("49"),
+    safari: version("16.4"),
+    ios: version("16.4"),
+    samsung: version("8.0"),
+    node: version("8.10"),
+    electron: version("3.0"),
+    ..Default::default()
+  }
+}
+
+// /a/d
+pub fn has_indices_data() -> Versions {
+  BrowserData {
+    chrome: version("90"),
+    edge: version("90"),
+    firefox: version("88"),
+    opera: version("76"),
+    safari: version("15"),
+    ios: version("15"),
+    samsung: version("15.0"),
+    node: version("16.0"),
+    electron: version("12.0"),
+    ..Default::default()
+  }
+}
+
+// (?i:a)
+pub fn modifiers_data() -> Versions {
+  BrowserData {
+    chrome: version("125"),
+    edge: version("125"),
+    firefox: version("132"),
+    opera: version("111"),
+    node: version("23.0"),
+    electron: version("31.0"),
+    ..Default::default()
+  }
+}
+
+// 1n
+pub fn big_int_data() -> Versions {
+  BrowserData {
+    chrome: version("67"),
+    edge: version("79"),
+    firefox: version("68"),
+    opera: version("54"),
+    safari: version("14"),
+    ios: version("14"),
+    samsung: version("9.0"),
+    node: version("10.4"),
+    electron: version("4.0"),
+    ..Default::default()
+  }
+}
+
+// import('./a.js')
+pub fn dynamic_import_data() -> Versions {
+  BrowserData {
+    chrome: version("63"),
+    edge: version("79"),
+    firefox: version("67"),
+    opera: version("50"),
+    safari: version("11.1"),
+    ios: version("11.3"),
+    samsung: version("8.0"),
+    node: version("13.2"),
+    electron: version("3.0"),
+    ..Default::default()
+  }
+}
+
+// import.meta
+pub fn import_meta_data() -> Versions {
+  BrowserData {
+    chrome: version("64"),
+    edge: version("79"),
+    firefox: version("62"),
+    opera: version("51"),
+    safari: version("11.1"),
+    ios: version("12"),
+    samsung: version("9.0"),
+    node: version("10.4"),
+    electron: version("3.0"),
+    ..Default::default()
+  }
+}
+
+// await at the top level of a module
+pub fn top_level_await_data() -> Versions {
+  BrowserData {
+    chrome: version("89"),
+    edge: version("89"),
+    firefox: version("89"),
+    opera: version("75"),
+    safari: version("15"),
+    ios: version("15"),
+    samsung: version("15.0"),
+    node: version("14.8"),
+    electron: version("12.0"),
+    ..Default::default()
+  }
+}
+
+// a instanceof B, honoring Symbol.hasInstance
+pub fn instance_of_data() -> Versions {
+  BrowserData {
+    chrome: version("51"),
+    edge: version("15"),
+    firefox: version("50"),
+    opera: version("38"),
+    safari: version("10"),
+    ios: version("10"),
+    samsung: version("5.0"),
+    node: version("6.5"),
+    electron: version("1.2"),
+    ..Default::default()
+  }
+}
+
+// function a(x,) {} | a(x,)
+pub fn trailing_function_commas_data() -> Versions {
+  BrowserData {
+    chrome: version("58"),
+    edge: version("14"),
+    firefox: version("52"),
+    opera: version("45"),
+    safari: version("10"),
+    ios: version("10"),
+    samsung: version("7.0"),
+    node: version("8.0"),
+    electron: version("1.7"),
+    ..Default::default()
+  }
+}
+
+// #!/usr/bin/env node
+pub fn hashbang_data() -> Versions {
+  BrowserData {
+    chrome: version("74"),
+    edge: version("79"),
+    firefox: version("67"),
+    opera: version("62"),
+    safari: version("13.1"),
+    ios: version("13.4"),
+    samsung: version("11.0"),
+    node: version("0.10"),
+    electron: version("6.0"),
+    ..Default::default()
+  }
+}
+
+// import a from "./a.json" with { type: "json" }
+pub fn import_attributes_data() -> Versions {
+  BrowserData {
+    chrome: version("123"),
+    edge: version("123"),
+    firefox: version("138"),
+    opera: version("109"),
+    safari: version("17.2"),
+    ios: version("17.2"),
+    node: version("18.20"),
+    electron: version("30.0"),
+    ..Default::default()
+  }
+}
+
+// /(?<year>\d{4})-\d{2}|\d{2}-(?<year>\d{4})/
+pub fn duplicate_named_groups_data() -> Versions {
+  BrowserData {
+    chrome: version("125"),
+    edge: version("125"),
+    firefox: version("129"),
+    opera: version("111"),
+    safari: version("17"),
+    ios: version("17"),
+    node: version("23.0"),
+    electron: version("31.0"),
+    ..Default::default()
+  }
+}
+
+// using res = getResource()
+pub fn explicit_resource_management_data() -> Versions {
+  BrowserData {
+    chrome: version("134"),
+    edge: version("134"),
+    firefox: version("141"),
+    opera: version("119"),
+    node: version("24.0"),
+    electron: version("35.0"),
+    ..Default::default()
+  }
+}
+
+// Not shipped by any browser yet
+
+// @decorator class A {}
+pub fn decorators_data() -> Versions {
+  BrowserData::default()
+}
+
+// class A { accessor x = 1 }
+pub fn auto_accessors_data() -> Versions {
+  BrowserData::default()
+}
@@ -1,23 +1,91 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use anyhow::Context;
 use preset_env_base::query::targets_to_versions;
-use preset_env_base::version::should_enable;
-use swc_core::common::{sync::Lrc, SourceFile, SourceMap, Span, Spanned};
-use swc_core::ecma::ast::EsVersion;
+use preset_env_base::version::{should_enable, Version};
+use swc_core::common::{
+  sync::Lrc, BytePos, Mark, SourceFile, SourceMap, Span, Spanned, SyntaxContext,
+};
 use swc_core::ecma::ast::*;
 use swc_core::ecma::visit::{noop_visit_type, Visit, VisitWith};
 use swc_ecma_preset_env::{Config, Feature, FeatureOrModule, Versions};
 
+use crate::bugfixes::{BugfixFeature, BugfixMatch};
+use crate::builtins::{required_modules, BuiltinMatch};
+use crate::data::{
+  auto_accessors_data, big_int_data, decorators_data, duplicate_named_groups_data,
+  dynamic_import_data, explicit_resource_management_data, has_indices_data, hashbang_data,
+  import_attributes_data, import_meta_data, instance_of_data, lookbehind_data, modifiers_data,
+  top_level_await_data, trailing_function_commas_data,
+};
+use crate::guards::Guard;
+use crate::node::NodeApiMatch;
+use crate::regexp::{analyze_regex, RegexFeature};
+use crate::runtime::is_runtime_stmt;
+use crate::tokens::{TokenFeature, TokenMatch};
+use crate::web_apis::WebApiMatch;
+
+// swc's EsVersion stops at es2022, features after it need their own years
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum EsVersion {
+  Es3,
+  Es5,
+  Es2015,
+  Es2016,
+  Es2017,
+  Es2018,
+  Es2019,
+  Es2020,
+  Es2021,
+  Es2022,
+  Es2023,
+  Es2024,
+  Es2025,
+  EsNext,
+}
+
+// features in `DATA_FEATURES` picked by `include`/`exclude`
+#[derive(Debug, Clone, Default)]
+pub struct DataConfig {
+  pub include: Vec<&'static str>,
+  pub exclude: Vec<&'static str>,
+}
+
+// features checked against our own compat data, named like their flags
+pub const DATA_FEATURES: &[&str] = &[
+  "auto_accessors",
+  "decorators",
+  "explicit_resource_management",
+  "trailing_function_commas",
+  "lookbehind_regex",
+  "has_indices_regex",
+  "regex_modifiers",
+  "duplicate_named_groups_regex",
+  "instance_of",
+  "big_int",
+  "dynamic_import",
+  "import_meta",
+  "top_level_await",
+  "import_attributes",
+  "hashbang",
+];
+
 pub fn compat(
   es_version: EsVersion,
   source_map: Lrc<SourceMap>,
   source_file: Lrc<SourceFile>,
   c: Config,
-) -> ESC {
-  let targets: Versions = targets_to_versions(c.targets).expect("failed to parse targets");
+  data: DataConfig,
+  skip_runtime: bool,
+  unresolved_mark: Mark,
+) -> Result<ESC, anyhow::Error> {
+  let targets: Versions = targets_to_versions(c.targets).context("invalid browserslist")?;
   let is_any_target = targets.is_any_target();
-  let (include, _included_modules) = FeatureOrModule::split(c.include);
-  let (exclude, _excluded_modules) = FeatureOrModule::split(c.exclude);
+  let (include, included_modules) = FeatureOrModule::split(c.include);
+  let (exclude, excluded_modules) = FeatureOrModule::split(c.exclude);
+  let mut required_builtins = required_modules(targets);
+  required_builtins.extend(included_modules);
+  required_builtins.retain(|module| !excluded_modules.contains(module));
   macro_rules! should_enable {
     ($feature:ident, $default:expr) => {{
       let f = Feature::$feature;
@@ -28,11 +96,37 @@ pub fn compat(
             || f.should_enable(targets, c.bugfixes, $default)))
     }};
   }
-  ESC {
+  // features without a preset-env plugin, checked against our own compat data
+  macro_rules! should_enable_data {
+    ($feature:ident, $data:expr) => {{
+      let f = stringify!($feature);
+      !data.exclude.contains(&f)
+        && ((c.force_all_transforms && is_transformable(f))
+          || is_any_target
+          || data.include.contains(&f)
+          || should_enable(targets, $data, false))
+    }};
+  }
+  Ok(ESC {
     flags: FeaturesFlag {
       class_static_block: should_enable!(ClassStaticBlock, false) || es_version < EsVersion::Es2022,
       private_methods: should_enable!(PrivateMethods, false) || es_version < EsVersion::Es2022,
       class_properties: should_enable!(ClassProperties, false) || es_version < EsVersion::Es2022,
+      private_fields: should_enable!(ClassProperties, false) || es_version < EsVersion::Es2022,
+      static_private: should_enable!(ClassProperties, false)
+        || should_enable!(PrivateMethods, false)
+        || es_version < EsVersion::Es2022,
+      private_property_in_object: should_enable!(PrivatePropertyInObject, false)
+        || es_version < EsVersion::Es2022,
+      // stage 3 proposals
+      auto_accessors: should_enable_data!(auto_accessors, auto_accessors_data())
+        || es_version < EsVersion::EsNext,
+      decorators: should_enable_data!(decorators, decorators_data())
+        || es_version < EsVersion::EsNext,
+      explicit_resource_management: should_enable_data!(
+        explicit_resource_management,
+        explicit_resource_management_data()
+      ) || es_version < EsVersion::EsNext,
       logical_assignment_operators: should_enable!(LogicalAssignmentOperators, false)
         || es_version < EsVersion::Es2021,
       nullish_coalescing: should_enable!(NullishCoalescing, false)
@@ -40,9 +134,18 @@ pub fn compat(
       optional_chaining: should_enable!(OptionalChaining, false) || es_version < EsVersion::Es2020,
       optional_catch_binding: should_enable!(OptionalCatchBinding, false)
         || es_version < EsVersion::Es2019,
+      json_strings: should_enable!(JsonStrings, false) || es_version < EsVersion::Es2019,
       // https://babeljs.io/docs/babel-plugin-transform-object-rest-spread
       object_rest_spread: should_enable!(ObjectRestSpread, false) || es_version < EsVersion::Es2018,
+      // https://babeljs.io/docs/babel-plugin-transform-async-generator-functions
+      async_generator_functions: should_enable!(AsyncGeneratorFunctions, false)
+        || es_version < EsVersion::Es2018,
+      for_await: should_enable!(AsyncGeneratorFunctions, false) || es_version < EsVersion::Es2018,
       async_to_generator: should_enable!(AsyncToGenerator, false) || es_version < EsVersion::Es2017,
+      trailing_function_commas: should_enable_data!(
+        trailing_function_commas,
+        trailing_function_commas_data()
+      ) || es_version < EsVersion::Es2017,
       exponentiation_operator: should_enable!(ExponentiationOperator, false)
         || es_version < EsVersion::Es2016,
       // alias es6
@@ -52,6 +155,25 @@ pub fn compat(
       spread: should_enable!(Spread, false) || es_version < EsVersion::Es2015,
       template_literals: should_enable!(TemplateLiterals, false) || es_version < EsVersion::Es2015,
       sticky_regex: should_enable!(StickyRegex, false) || es_version < EsVersion::Es2015,
+      unicode_regex: should_enable!(UnicodeRegex, false) || es_version < EsVersion::Es2015,
+      dot_all_regex: should_enable!(DotAllRegex, false) || es_version < EsVersion::Es2018,
+      named_capturing_groups_regex: should_enable!(NamedCapturingGroupsRegex, false)
+        || es_version < EsVersion::Es2018,
+      unicode_property_regex: should_enable!(UnicodePropertyRegex, false)
+        || es_version < EsVersion::Es2018,
+      lookbehind_regex: should_enable_data!(lookbehind_regex, lookbehind_data())
+        || es_version < EsVersion::Es2018,
+      has_indices_regex: should_enable_data!(has_indices_regex, has_indices_data())
+        || es_version < EsVersion::Es2022,
+      // es2024
+      unicode_sets_regex: should_enable!(UnicodeSetsRegex, false) || es_version < EsVersion::Es2024,
+      // es2025
+      regex_modifiers: should_enable_data!(regex_modifiers, modifiers_data())
+        || es_version < EsVersion::Es2025,
+      duplicate_named_groups_regex: should_enable_data!(
+        duplicate_named_groups_regex,
+        duplicate_named_groups_data()
+      ) || es_version < EsVersion::Es2025,
       shorthand_properties: should_enable!(ShorthandProperties, false)
         || es_version < EsVersion::Es2015,
       computed_properties: should_enable!(ComputedProperties, false)
@@ -59,24 +181,70 @@ pub fn compat(
       destructuring: should_enable!(Destructuring, false) || es_version < EsVersion::Es2015,
       classes: should_enable!(Classes, false) || es_version < EsVersion::Es2015,
       regenerator: should_enable!(Regenerator, false) || es_version < EsVersion::Es2015,
-      // duplicate_keys: should_enable!(DuplicateKeys, false) || es_version < EsVersion::Es2015,
-      // instanceOf: should_enable!(InstanceOf, false) || es_version < EsVersion::Es2015,
+      duplicate_keys: should_enable!(DuplicateKeys, false) || es_version < EsVersion::Es2015,
+      instance_of: should_enable_data!(instance_of, instance_of_data())
+        || es_version < EsVersion::Es2015,
       for_of: should_enable!(ForOf, false) || es_version < EsVersion::Es2015,
-      // TODO: Looks like webpack runtime code always contain sho
       function_name: should_enable!(FunctionName, false) || es_version < EsVersion::Es2015,
-      // literals: should_enable!(Literals, false) || es_version < EsVersion::Es2015,
+      binary_literals: should_enable!(Literals, false) || es_version < EsVersion::Es2015,
+      octal_literals: should_enable!(Literals, false) || es_version < EsVersion::Es2015,
+      numeric_separator: should_enable!(NumericSeparator, false) || es_version < EsVersion::Es2021,
+      big_int: should_enable_data!(big_int, big_int_data()) || es_version < EsVersion::Es2020,
       new_target: should_enable!(NewTarget, false) || es_version < EsVersion::Es2015,
+      // module syntax
+      dynamic_import: should_enable_data!(dynamic_import, dynamic_import_data())
+        || es_version < EsVersion::Es2020,
+      import_meta: should_enable_data!(import_meta, import_meta_data())
+        || es_version < EsVersion::Es2020,
+      export_namespace_from: should_enable!(ExportNamespaceFrom, false)
+        || es_version < EsVersion::Es2020,
+      top_level_await: should_enable_data!(top_level_await, top_level_await_data())
+        || es_version < EsVersion::Es2022,
+      // es2025
+      import_attributes: should_enable_data!(import_attributes, import_attributes_data())
+        || es_version < EsVersion::Es2025,
+      // es2023
+      hashbang: should_enable_data!(hashbang, hashbang_data()) || es_version < EsVersion::Es2023,
       object_super: should_enable!(ObjectSuper, false) || es_version < EsVersion::Es2015,
       typeof_symbol: should_enable!(TypeOfSymbol, false) || es_version < EsVersion::Es2015,
-      // unicode_escapes: should_enable!(UnicodeEscapes, false) || es_version < EsVersion::Es2015,
-      // unicode_regex: should_enable!(UnicodeRegex, false) || es_version < EsVersion::Es2015,
+      unicode_escapes: should_enable!(UnicodeEscapes, false) || es_version < EsVersion::Es2015,
+      // es5
+      member_expression_literals: should_enable!(MemberExpressionLiterals, false)
+        || es_version < EsVersion::Es5,
+      property_literals: should_enable!(PropertyLiterals, false) || es_version < EsVersion::Es5,
+      // every browserslist browser supports es5, only an es3 `target` reports these
+      property_mutators: es_version < EsVersion::Es5,
+      trailing_literal_commas: es_version < EsVersion::Es5,
+      es5_builtins: es_version < EsVersion::Es5,
+      // engine bugs, without `bugfixes` the whole feature is reported instead
+      bugfix_safari_block_shadowing: c.bugfixes
+        && should_enable!(BugfixTransformSafariBlockShadowing, false),
+      bugfix_safari_id_destructuring_collision: c.bugfixes
+        && should_enable!(
+          BugfixSafariIdDestructuringCollisionInFunctionExpression,
+          false
+        ),
+      bugfix_edge_default_parameters: c.bugfixes && should_enable!(BugfixEdgeDefaultParam, false),
+      bugfix_tagged_template_caching: c.bugfixes
+        && should_enable!(BugfixTaggedTemplateCaching, false),
+      bugfix_v8_spread_parameters_in_optional_chaining: c.bugfixes
+        && should_enable!(BugfixTransformV8SpreadParametersInOptionalChaining, false),
     },
     source_file,
     source_map,
     details: vec![],
     features: FeaturesFlag::default(),
     es_versions: HashMap::new(),
-  }
+    function_depth: 0,
+    in_object_method: false,
+    runtime_depth: 0,
+    runtime_spans: vec![],
+    skip_runtime,
+    required_builtins,
+    targets,
+    unresolved: SyntaxContext::empty().apply_mark(unresolved_mark),
+    guards: vec![],
+  })
 }
 #[napi(object)]
 #[derive(Debug, Default, Clone)]
@@ -86,6 +254,8 @@ pub struct FeaturesFlag {
   pub new_target: bool,
   pub object_super: bool,
   pub typeof_symbol: bool,
+  pub duplicate_keys: bool,
+  pub instance_of: bool,
   pub for_of: bool,
   pub classes: bool,
   pub spread: bool,
@@ -94,6 +264,15 @@ pub struct FeaturesFlag {
   pub computed_properties: bool,
   pub shorthand_properties: bool,
   pub sticky_regex: bool,
+  pub unicode_regex: bool,
+  pub dot_all_regex: bool,
+  pub named_capturing_groups_regex: bool,
+  pub unicode_property_regex: bool,
+  pub lookbehind_regex: bool,
+  pub has_indices_regex: bool,
+  pub unicode_sets_regex: bool,
+  pub regex_modifiers: bool,
+  pub duplicate_named_groups_regex: bool,
   pub template_literals: bool,
   pub parameters: bool,
   pub arrow_functions: bool,
@@ -102,11 +281,42 @@ pub struct FeaturesFlag {
   pub class_static_block: bool,
   pub private_methods: bool,
   pub async_to_generator: bool,
+  pub trailing_function_commas: bool,
   pub logical_assignment_operators: bool,
   pub nullish_coalescing: bool,
   pub object_rest_spread: bool,
+  pub async_generator_functions: bool,
+  pub for_await: bool,
   pub optional_chaining: bool,
   pub optional_catch_binding: bool,
+  pub json_strings: bool,
+  pub binary_literals: bool,
+  pub octal_literals: bool,
+  pub numeric_separator: bool,
+  pub big_int: bool,
+  pub unicode_escapes: bool,
+  pub member_expression_literals: bool,
+  pub property_literals: bool,
+  pub property_mutators: bool,
+  pub trailing_literal_commas: bool,
+  pub es5_builtins: bool,
+  pub dynamic_import: bool,
+  pub import_meta: bool,
+  pub export_namespace_from: bool,
+  pub top_level_await: bool,
+  pub import_attributes: bool,
+  pub hashbang: bool,
+  pub private_fields: bool,
+  pub static_private: bool,
+  pub private_property_in_object: bool,
+  pub auto_accessors: bool,
+  pub decorators: bool,
+  pub explicit_resource_management: bool,
+  pub bugfix_safari_block_shadowing: bool,
+  pub bugfix_safari_id_destructuring_collision: bool,
+  pub bugfix_edge_default_parameters: bool,
+  pub bugfix_tagged_template_caching: bool,
+  pub bugfix_v8_spread_parameters_in_optional_chaining: bool,
 }
 
 #[napi(object)]
@@ -128,6 +338,22 @@ pub struct Detail {
   pub ls: Line,
   // end line and col
   pub le: Line,
+  // false if no transform can downlevel it, e.g. BigInt
+  pub transformable: bool,
+  // "runtime" inside code injected by bundlers, e.g. webpack bootstrap
+  pub origin: Option<String>,
+  // "builtin" for APIs polyfilled by core-js, "web-api" for DOM and web platform APIs, "node"
+  // for Node.js APIs, unset for syntax
+  pub kind: Option<String>,
+  // first Node.js version shipping it, only for "node"
+  pub since: Option<String>,
+  // true if the API is only used behind a feature check, e.g. `typeof Symbol !== 'undefined'`
+  pub guarded: Option<bool>,
+  // true if the global is shadowed by a local binding, e.g. a bundled Promise helper. Not an
+  // actual use of the API
+  pub excluded: Option<bool>,
+  // true if matched by a member name on a receiver of unknown type, e.g. `a.at(-1)`
+  pub low_confidence: Option<bool>,
 }
 
 #[derive(Clone)]
@@ -138,6 +364,22 @@ pub struct ESC {
   pub details: Vec<Detail>,
   source_map: Lrc<SourceMap>,
   source_file: Lrc<SourceFile>,
+  // 0 at the top level of the module
+  function_depth: u32,
+  // inside a method, getter or setter of an object literal, where `super` is the prototype
+  in_object_method: bool,
+  // > 0 inside bundler runtime code
+  runtime_depth: u32,
+  // statements injected by bundlers, for findings outside the AST walk
+  runtime_spans: Vec<Span>,
+  skip_runtime: bool,
+  // core-js modules the targets need
+  required_builtins: HashSet<String>,
+  targets: Versions,
+  // context of identifiers the resolver found no binding for, i.e. globals
+  unresolved: SyntaxContext,
+  // regions only reached when an API is present, see `set_guards`
+  guards: Vec<Guard>,
 }
 
 impl ESC {
@@ -145,11 +387,6 @@ impl ESC {
     let real_span = self.source_map.span_to_char_offset(&self.source_file, span);
     (real_span.0 as i32, real_span.1 as i32)
   }
-  fn get_real_span_from_range(&self, lo: Span, hi: Span) -> (i32, i32) {
-    let real_span_lo = self.source_map.span_to_char_offset(&self.source_file, lo);
-    let real_span_hi = self.source_map.span_to_char_offset(&self.source_file, hi);
-    (real_span_lo.0 as i32, real_span_hi.1 as i32)
-  }
   fn get_real_loc(&self, span: Span) -> (Line, Line) {
     let line_lo = self.source_map.lookup_char_pos(span.lo());
     let line_hi = self.source_map.lookup_char_pos(span.hi());
@@ -164,71 +401,509 @@ impl ESC {
       },
     )
   }
-  fn get_real_loc_from_range(&self, lo: Span, hi: Span) -> (Line, Line) {
-    let line_lo = self.source_map.lookup_char_pos(lo.lo());
-    let line_hi = self.source_map.lookup_char_pos(hi.hi());
-    (
-      Line {
-        l: line_lo.line as i32,
-        c: line_lo.col.0 as i32,
-      },
-      Line {
-        l: line_hi.line as i32,
-        c: line_hi.col.0 as i32,
-      },
-    )
+  fn origin(&self) -> Option<String> {
+    if self.runtime_depth > 0 {
+      Some(String::from("runtime"))
+    } else {
+      None
+    }
   }
   fn add_detail(&mut self, span: Span, feature: String) {
+    self.add_detail_with_kind(span, feature, None);
+  }
+  fn add_detail_with_kind(&mut self, span: Span, feature: String, kind: Option<String>) {
     let real_span = self.get_real_span(span);
     let loc = self.get_real_loc(span);
+    // no polyfill in core-js for web and Node.js APIs
+    let transformable =
+      !matches!(kind.as_deref(), Some("web-api" | "node")) && is_transformable(&feature);
+    let origin = self.origin();
+    // syntax can't be guarded, an unsupported one fails the whole file
+    let guarded = kind.is_some() && self.guards.iter().any(|guard| guard.covers(span, &feature));
     self.details.push(Detail {
       feature,
       s: real_span.0,
       e: real_span.1,
       ls: loc.0,
       le: loc.1,
+      transformable,
+      origin,
+      kind,
+      since: None,
+      guarded: guarded.then_some(true),
+      excluded: None,
+      low_confidence: None,
     });
   }
+  fn check_numeric_raw(&mut self, span: Span, raw: &str) {
+    let prefix = raw.get(..2).map(|p| p.to_ascii_lowercase());
+    if prefix.as_deref() == Some("0b") && self.flags.binary_literals {
+      self.add_detail(span, String::from("binary_literals"));
+      self.features.binary_literals = true;
+      self.es_versions.insert(EsVersion::Es2015, true);
+    }
+    if prefix.as_deref() == Some("0o") && self.flags.octal_literals {
+      self.add_detail(span, String::from("octal_literals"));
+      self.features.octal_literals = true;
+      self.es_versions.insert(EsVersion::Es2015, true);
+    }
+    if raw.contains('_') && self.flags.numeric_separator {
+      self.add_detail(span, String::from("numeric_separator"));
+      self.features.numeric_separator = true;
+      self.es_versions.insert(EsVersion::Es2021, true);
+    }
+  }
+  // anonymous function and class expressions get their name from the binding
+  fn check_function_name(&mut self, expr: &Expr) {
+    let span = match expr.unwrap_parens() {
+      Expr::Fn(FnExpr {
+        ident: None,
+        function,
+      }) => function.span,
+      Expr::Class(ClassExpr { ident: None, class }) => class.span,
+      _ => return,
+    };
+    if self.flags.function_name {
+      self.add_detail(span, String::from("function_name"));
+      self.features.function_name = true;
+      self.es_versions.insert(EsVersion::Es2015, true);
+    }
+  }
+  // const { a } = b | const { a, ...rest } = b
+  fn check_destructuring(&mut self, span: Span, pat: &Pat) {
+    if contains_destructuring(pat) && !contains_object_rest(pat) && self.flags.destructuring {
+      self.add_detail(span, String::from("destructuring"));
+      self.features.destructuring = true;
+      self.es_versions.insert(EsVersion::Es2015, true);
+    }
+    if contains_object_rest(pat) && self.flags.object_rest_spread {
+      self.add_detail(span, String::from("object_rest_spread"));
+      self.features.object_rest_spread = true;
+      self.es_versions.insert(EsVersion::Es2018, true);
+    }
+  }
+  // `arg` is the operand of `typeof`. `function f(Symbol) {}` shadows the global, the finding is
+  // excluded
+  fn check_typeof_symbol(&mut self, span: Span, arg: &Expr) {
+    self.add_detail(span, String::from("typeof_symbol"));
+    if symbol_ident(arg).is_some_and(|ident| ident.span.ctxt != self.unresolved) {
+      self.exclude_last_detail();
+      return;
+    }
+    self.features.typeof_symbol = true;
+    self.es_versions.insert(EsVersion::Es2015, true);
+  }
+  // `span` covers the function owning `params`
+  fn check_params(&mut self, span: Span, params: &[&Pat]) {
+    // function a({ x, y }) {} | function a({ x, ...rest }) {}
+    for param in params {
+      self.check_destructuring(param.span(), param);
+    }
+    // function (x=1) {} | function (...args) {}
+    if params
+      .iter()
+      .any(|param| matches!(param, Pat::Assign(..) | Pat::Rest(..)))
+      && self.flags.parameters
+    {
+      self.add_detail(span, String::from("parameters"));
+      self.es_versions.insert(EsVersion::Es2015, true);
+      self.features.parameters = true;
+    }
+  }
+  // static #x = 1 | static #x() {}
+  fn check_static_private(&mut self, span: Span) {
+    if self.flags.static_private {
+      self.add_detail(span, String::from("static_private"));
+      self.es_versions.insert(EsVersion::Es2022, true);
+      self.features.static_private = true;
+    }
+  }
+  fn check_top_level_await(&mut self, span: Span) {
+    if self.flags.top_level_await {
+      self.add_detail(span, String::from("top_level_await"));
+      self.features.top_level_await = true;
+      self.es_versions.insert(EsVersion::Es2022, true);
+    }
+  }
+  fn check_import_attributes(&mut self, span: Span) {
+    if self.flags.import_attributes {
+      self.add_detail(span, String::from("import_attributes"));
+      self.features.import_attributes = true;
+      self.es_versions.insert(EsVersion::Es2025, true);
+    }
+  }
+  // `raw` is the source text of `span`
+  fn check_unicode_escapes(&mut self, span: Span, raw: &str) {
+    if !self.flags.unicode_escapes {
+      return;
+    }
+    for (lo, hi) in code_point_escapes(raw) {
+      let inner = Span::new(
+        span.lo + BytePos(lo as u32),
+        span.lo + BytePos(hi as u32),
+        span.ctxt,
+      );
+      self.add_detail(inner, String::from("unicode_escapes"));
+      self.features.unicode_escapes = true;
+      self.es_versions.insert(EsVersion::Es2015, true);
+    }
+  }
+  // `span` covers the whole `/exp/flags` literal, or the `RegExp(...)` call when `literal` is false
+  fn check_regex(&mut self, span: Span, exp: &str, flags: &str, literal: bool) {
+    for m in analyze_regex(exp, flags) {
+      let (enabled, found, feature, es_version) = match m.feature {
+        RegexFeature::StickyFlag => (
+          self.flags.sticky_regex,
+          &mut self.features.sticky_regex,
+          "sticky_regex",
+          EsVersion::Es2015,
+        ),
+        RegexFeature::UnicodeFlag => (
+          self.flags.unicode_regex,
+          &mut self.features.unicode_regex,
+          "unicode_regex",
+          EsVersion::Es2015,
+        ),
+        RegexFeature::DotAllFlag => (
+          self.flags.dot_all_regex,
+          &mut self.features.dot_all_regex,
+          "dot_all_regex",
+          EsVersion::Es2018,
+        ),
+        RegexFeature::NamedCapturingGroups => (
+          self.flags.named_capturing_groups_regex,
+          &mut self.features.named_capturing_groups_regex,
+          "named_capturing_groups_regex",
+          EsVersion::Es2018,
+        ),
+        RegexFeature::UnicodePropertyEscapes => (
+          self.flags.unicode_property_regex,
+          &mut self.features.unicode_property_regex,
+          "unicode_property_regex",
+          EsVersion::Es2018,
+        ),
+        RegexFeature::Lookbehind => (
+          self.flags.lookbehind_regex,
+          &mut self.features.lookbehind_regex,
+          "lookbehind_regex",
+          EsVersion::Es2018,
+        ),
+        RegexFeature::HasIndicesFlag => (
+          self.flags.has_indices_regex,
+          &mut self.features.has_indices_regex,
+          "has_indices_regex",
+          EsVersion::Es2022,
+        ),
+        RegexFeature::UnicodeSetsFlag => (
+          self.flags.unicode_sets_regex,
+          &mut self.features.unicode_sets_regex,
+          "unicode_sets_regex",
+          EsVersion::Es2024,
+        ),
+        RegexFeature::Modifiers => (
+          self.flags.regex_modifiers,
+          &mut self.features.regex_modifiers,
+          "regex_modifiers",
+          EsVersion::Es2025,
+        ),
+        RegexFeature::DuplicateNamedGroups => (
+          self.flags.duplicate_named_groups_regex,
+          &mut self.features.duplicate_named_groups_regex,
+          "duplicate_named_groups_regex",
+          EsVersion::Es2025,
+        ),
+      };
+      if !enabled {
+        continue;
+      }
+      *found = true;
+      self.es_versions.insert(es_version, true);
+      if literal {
+        let inner = Span::new(span.lo + BytePos(m.lo), span.lo + BytePos(m.hi), span.ctxt);
+        self.add_detail(inner, String::from(feature));
+      } else {
+        self.add_detail(span, String::from(feature));
+      }
+    }
+  }
+  // findings of the token pass, run after the AST walk
+  pub fn check_tokens(&mut self, matches: Vec<TokenMatch>) {
+    for m in matches {
+      let (enabled, found, feature, es_version): (_, fn(&mut FeaturesFlag) -> &mut bool, _, _) =
+        match m.feature {
+          TokenFeature::TrailingFunctionComma => (
+            self.flags.trailing_function_commas,
+            |features| &mut features.trailing_function_commas,
+            "trailing_function_commas",
+            EsVersion::Es2017,
+          ),
+          TokenFeature::LineSeparator => (
+            self.flags.json_strings,
+            |features| &mut features.json_strings,
+            "json_strings",
+            EsVersion::Es2019,
+          ),
+          TokenFeature::TrailingLiteralComma => (
+            self.flags.trailing_literal_commas,
+            |features| &mut features.trailing_literal_commas,
+            "trailing_literal_commas",
+            EsVersion::Es5,
+          ),
+          TokenFeature::Hashbang => (
+            self.flags.hashbang,
+            |features| &mut features.hashbang,
+            "hashbang",
+            EsVersion::Es2023,
+          ),
+        };
+      if !enabled || !self.add_detail_after_walk(m.span, String::from(feature), None) {
+        continue;
+      }
+      *found(&mut self.features) = true;
+      self.es_versions.insert(es_version, true);
+    }
+  }
+  // API findings inside these regions are marked `guarded`
+  pub fn set_guards(&mut self, guards: Vec<Guard>) {
+    self.guards = guards;
+  }
+  // findings of the bugfix pass, run after the AST walk. Nothing to report when the targets
+  // need the whole feature transformed anyway
+  pub fn check_bugfixes(&mut self, matches: Vec<BugfixMatch>) {
+    for m in matches {
+      let (enabled, found, feature, es_version): (_, fn(&mut FeaturesFlag) -> &mut bool, _, _) =
+        match m.feature {
+          BugfixFeature::SafariBlockShadowing => (
+            self.flags.bugfix_safari_block_shadowing && !self.flags.block_scoping,
+            |features| &mut features.bugfix_safari_block_shadowing,
+            "bugfix_safari_block_shadowing",
+            EsVersion::Es2015,
+          ),
+          BugfixFeature::SafariIdDestructuringCollision => (
+            self.flags.bugfix_safari_id_destructuring_collision && !self.flags.parameters,
+            |features| &mut features.bugfix_safari_id_destructuring_collision,
+            "bugfix_safari_id_destructuring_collision",
+            EsVersion::Es2015,
+          ),
+          BugfixFeature::EdgeDefaultParameters => (
+            self.flags.bugfix_edge_default_parameters && !self.flags.parameters,
+            |features| &mut features.bugfix_edge_default_parameters,
+            "bugfix_edge_default_parameters",
+            EsVersion::Es2015,
+          ),
+          BugfixFeature::TaggedTemplateCaching => (
+            self.flags.bugfix_tagged_template_caching && !self.flags.template_literals,
+            |features| &mut features.bugfix_tagged_template_caching,
+            "bugfix_tagged_template_caching",
+            EsVersion::Es2015,
+          ),
+          BugfixFeature::V8SpreadParametersInOptionalChaining => (
+            self.flags.bugfix_v8_spread_parameters_in_optional_chaining
+              && !self.flags.optional_chaining,
+            |features| &mut features.bugfix_v8_spread_parameters_in_optional_chaining,
+            "bugfix_v8_spread_parameters_in_optional_chaining",
+            EsVersion::Es2020,
+          ),
+        };
+      if !enabled || !self.add_detail_after_walk(m.span, String::from(feature), None) {
+        continue;
+      }
+      *found(&mut self.features) = true;
+      self.es_versions.insert(es_version, true);
+    }
+  }
+  // findings of the web API pass, run after the AST walk
+  pub fn check_web_apis(&mut self, matches: Vec<WebApiMatch>) {
+    for m in matches {
+      if !(self.targets.is_any_target() || should_enable(self.targets, m.support, false))
+        || !self.add_detail_after_walk(m.span, m.name, Some(String::from("web-api")))
+      {
+        continue;
+      }
+      if m.shadowed {
+        self.exclude_last_detail();
+      }
+      if m.low_confidence {
+        self.mark_last_detail_low_confidence();
+      }
+    }
+  }
+  // findings of the Node.js pass, run after the AST walk. `targets` only has a node version
+  pub fn check_node_apis(&mut self, matches: Vec<NodeApiMatch>) {
+    for m in matches {
+      let since: Version = m.since.parse().expect("invalid node version");
+      if self.targets.node.map_or(true, |node| since > node)
+        && self.add_detail_after_walk(m.span, String::from(m.name), Some(String::from("node")))
+      {
+        if let Some(detail) = self.details.last_mut() {
+          detail.since = Some(String::from(m.since));
+        }
+        if m.shadowed {
+          self.exclude_last_detail();
+        }
+      }
+    }
+  }
+  // findings of the built-in pass, run after the AST walk
+  pub fn check_builtins(&mut self, matches: Vec<BuiltinMatch>) {
+    for m in matches {
+      if !self.required_builtins.contains(m.module)
+        || !self.add_detail_after_walk(m.span, String::from(m.name), Some(String::from("builtin")))
+      {
+        continue;
+      }
+      if m.shadowed {
+        self.exclude_last_detail();
+      }
+      if m.low_confidence {
+        self.mark_last_detail_low_confidence();
+      }
+    }
+  }
+  fn exclude_last_detail(&mut self) {
+    if let Some(detail) = self.details.last_mut() {
+      detail.excluded = Some(true);
+    }
+  }
+  fn mark_last_detail_low_confidence(&mut self) {
+    if let Some(detail) = self.details.last_mut() {
+      detail.low_confidence = Some(true);
+    }
+  }
+  // the AST walk is over, tell runtime code apart by the spans it recorded. Returns false if
+  // the finding is skipped
+  fn add_detail_after_walk(&mut self, span: Span, feature: String, kind: Option<String>) -> bool {
+    let runtime = self
+      .runtime_spans
+      .iter()
+      .any(|runtime| runtime.lo <= span.lo && span.hi <= runtime.hi);
+    if runtime && self.skip_runtime {
+      return false;
+    }
+    if runtime {
+      self.runtime_depth += 1;
+    }
+    self.add_detail_with_kind(span, feature, kind);
+    if runtime {
+      self.runtime_depth -= 1;
+    }
+    true
+  }
 }
 
 // https://github.com/sudheerj/ECMAScript-features
 impl Visit for ESC {
   noop_visit_type!();
 
-  // Webpack runtime code always contain function_name
-  // const a = function() {}
-  // fn visit_fn_expr(&mut self, n: &FnExpr) {
-  //   n.visit_children_with(self);
-  //   if self.flags.function_name {
-  //     // println!("function_name: {:?}", n.function.span);
-  //     self.features.function_name = true;
-  //     self.es_versions.insert(EsVersion::Es2015, true);
-  //   }
-  // }
-
-  // var a = class {}
-  // fn visit_class_expr(&mut self, n: &ClassExpr) {
-  //   println!("visit_class_expr {:?}", n.class.span);
-  //   n.visit_children_with(self);
-  //   if self.flags.function_name {
-  //     self.features.function_name = true;
-  //     self.es_versions.insert(EsVersion::Es2015, true);
-  //   }
-  // }
+  // bundler runtime, e.g. function __webpack_require__(moduleId) {}
+  fn visit_stmt(&mut self, n: &Stmt) {
+    if !is_runtime_stmt(n) {
+      n.visit_children_with(self);
+      return;
+    }
+    self.runtime_spans.push(n.span());
+    if self.skip_runtime {
+      return;
+    }
+    self.runtime_depth += 1;
+    n.visit_children_with(self);
+    self.runtime_depth -= 1;
+  }
+
+  // var a = function () {} | var a = class {}
+  fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+    n.visit_children_with(self);
+    if let (Pat::Ident(..), Some(init)) = (&n.name, &n.init) {
+      self.check_function_name(init);
+    }
+  }
+
+  // const obj = { a: function () {} }
+  fn visit_key_value_prop(&mut self, n: &KeyValueProp) {
+    n.visit_children_with(self);
+    if let PropName::Ident(..) | PropName::Str(..) = n.key {
+      self.check_function_name(&n.value);
+    }
+  }
 
   // new.target
+  // import.meta
   fn visit_meta_prop_expr(&mut self, n: &MetaPropExpr) {
     n.visit_children_with(self);
-    if self.flags.new_target {
-      self.add_detail(n.span, String::from("new_target"));
-      self.features.new_target = true;
-      self.es_versions.insert(EsVersion::Es2015, true);
+    match n.kind {
+      MetaPropKind::NewTarget => {
+        if self.flags.new_target {
+          self.add_detail(n.span, String::from("new_target"));
+          self.features.new_target = true;
+          self.es_versions.insert(EsVersion::Es2015, true);
+        }
+      }
+      MetaPropKind::ImportMeta => {
+        if self.flags.import_meta {
+          self.add_detail(n.span, String::from("import_meta"));
+          self.features.import_meta = true;
+          self.es_versions.insert(EsVersion::Es2020, true);
+        }
+      }
+    }
+  }
+
+  // await at the top level
+  fn visit_await_expr(&mut self, n: &AwaitExpr) {
+    n.visit_children_with(self);
+    if self.function_depth == 0 {
+      self.check_top_level_await(n.span);
+    }
+  }
+
+  // import a from "./a.json" with { type: "json" }
+  fn visit_import_decl(&mut self, n: &ImportDecl) {
+    n.visit_children_with(self);
+    if let Some(with) = &n.with {
+      self.check_import_attributes(with.span);
+    }
+  }
+
+  // export * from "./a.json" with { type: "json" }
+  fn visit_export_all(&mut self, n: &ExportAll) {
+    n.visit_children_with(self);
+    if let Some(with) = &n.with {
+      self.check_import_attributes(with.span);
+    }
+  }
+
+  // export * as ns from "./a"
+  fn visit_named_export(&mut self, n: &NamedExport) {
+    n.visit_children_with(self);
+    if let Some(with) = &n.with {
+      self.check_import_attributes(with.span);
+    }
+    for specifier in &n.specifiers {
+      if let ExportSpecifier::Namespace(namespace) = specifier {
+        if self.flags.export_namespace_from {
+          self.add_detail(namespace.span, String::from("export_namespace_from"));
+          self.features.export_namespace_from = true;
+          self.es_versions.insert(EsVersion::Es2020, true);
+        }
+      }
     }
   }
 
   // for of
   fn visit_for_of_stmt(&mut self, n: &ForOfStmt) {
     n.visit_children_with(self);
+    // for await (const a of b) {}
+    if n.is_await {
+      if self.function_depth == 0 {
+        self.check_top_level_await(n.span);
+      }
+      if self.flags.for_await {
+        self.add_detail(n.span, String::from("for_await"));
+        self.features.for_await = true;
+        self.es_versions.insert(EsVersion::Es2018, true);
+      }
+      return;
+    }
     if self.flags.for_of {
       self.add_detail(n.span, String::from("for_of"));
       self.features.for_of = true;
@@ -236,6 +911,52 @@ impl Visit for ESC {
     }
   }
 
+  // for ([k, v] of map) {}
+  fn visit_for_head(&mut self, n: &ForHead) {
+    n.visit_children_with(self);
+    if let ForHead::Pat(pat) = n {
+      self.check_destructuring(pat.span(), pat);
+    }
+  }
+
+  // class A { constructor({ a }, b = 1) {} }
+  fn visit_constructor(&mut self, n: &Constructor) {
+    n.visit_children_with(self);
+    let params: Vec<&Pat> = n
+      .params
+      .iter()
+      .filter_map(|param| match param {
+        ParamOrTsParamProp::Param(param) => Some(&param.pat),
+        _ => None,
+      })
+      .collect();
+    self.check_params(n.span, &params);
+  }
+
+  // const a = { set b({ c }) {} }
+  fn visit_setter_prop(&mut self, n: &SetterProp) {
+    n.visit_children_with(self);
+    self.check_params(n.span, &[&n.param]);
+  }
+
+  // `super` in class members is handled by the classes transform
+  fn visit_class(&mut self, n: &Class) {
+    let in_object_method = self.in_object_method;
+    self.in_object_method = false;
+    n.visit_children_with(self);
+    self.in_object_method = in_object_method;
+  }
+
+  // { a() { return super.a() } }
+  fn visit_super_prop_expr(&mut self, n: &SuperPropExpr) {
+    n.visit_children_with(self);
+    if self.in_object_method && self.flags.object_super {
+      self.add_detail(n.span, String::from("object_super"));
+      self.features.object_super = true;
+      self.es_versions.insert(EsVersion::Es2015, true);
+    }
+  }
+
   // Class
   fn visit_class_decl(&mut self, n: &ClassDecl) {
     n.visit_children_with(self);
@@ -246,6 +967,45 @@ impl Visit for ESC {
     }
   }
 
+  // const A = class {}
+  fn visit_class_expr(&mut self, n: &ClassExpr) {
+    n.visit_children_with(self);
+    if self.flags.classes {
+      self.add_detail(n.span(), String::from("classes"));
+      self.features.classes = true;
+      self.es_versions.insert(EsVersion::Es2015, true);
+    }
+  }
+
+  // class A { accessor x = 1 }
+  fn visit_auto_accessor(&mut self, n: &AutoAccessor) {
+    n.visit_children_with(self);
+    if self.flags.auto_accessors {
+      self.add_detail(n.span, String::from("auto_accessors"));
+      self.features.auto_accessors = true;
+      self.es_versions.insert(EsVersion::EsNext, true);
+    }
+  }
+
+  // @decorator class A {}
+  fn visit_decorator(&mut self, n: &Decorator) {
+    n.visit_children_with(self);
+    if self.flags.decorators {
+      self.add_detail(n.span, String::from("decorators"));
+      self.features.decorators = true;
+      self.es_versions.insert(EsVersion::EsNext, true);
+    }
+  }
+  // using res = getResource() | await using res = getResource()
+  fn visit_using_decl(&mut self, n: &UsingDecl) {
+    n.visit_children_with(self);
+    if self.flags.explicit_resource_management {
+      self.add_detail(n.span, String::from("explicit_resource_management"));
+      self.features.explicit_resource_management = true;
+      self.es_versions.insert(EsVersion::EsNext, true);
+    }
+  }
+
   // const obj = { ["key"]: value }
   fn visit_computed_prop_name(&mut self, n: &ComputedPropName) {
     n.visit_children_with(self);
@@ -269,7 +1029,12 @@ impl Visit for ESC {
   // Visit object prop
   // const obj = { a, b }
   fn visit_prop(&mut self, n: &Prop) {
+    let in_object_method = self.in_object_method;
+    if matches!(n, Prop::Method(..) | Prop::Getter(..) | Prop::Setter(..)) {
+      self.in_object_method = true;
+    }
     n.visit_children_with(self);
+    self.in_object_method = in_object_method;
     match n {
       Prop::Shorthand(..) | Prop::Method(..) => {
         if self.flags.shorthand_properties {
@@ -279,17 +1044,182 @@ impl Visit for ESC {
         }
         return;
       }
+      // { get a() {}, set a(v) {} }
+      Prop::Getter(..) | Prop::Setter(..) => {
+        if self.flags.property_mutators {
+          self.add_detail(n.span(), String::from("property_mutators"));
+          self.es_versions.insert(EsVersion::Es5, true);
+          self.features.property_mutators = true;
+        }
+      }
       _ => (),
     }
   }
-  // /Foo\s+(\d+)/y
+
+  // { default: 1 }
+  fn visit_prop_name(&mut self, n: &PropName) {
+    n.visit_children_with(self);
+    if let PropName::Ident(ident) = n {
+      if is_es3_reserved(&ident.sym) && self.flags.property_literals {
+        self.add_detail(ident.span, String::from("property_literals"));
+        self.es_versions.insert(EsVersion::Es5, true);
+        self.features.property_literals = true;
+      }
+    }
+  }
+
+  // a.default | Object.keys(a) | a.forEach(fn)
+  fn visit_member_expr(&mut self, n: &MemberExpr) {
+    n.visit_children_with(self);
+    let MemberProp::Ident(prop) = &n.prop else {
+      return;
+    };
+    if is_es3_reserved(&prop.sym) && self.flags.member_expression_literals {
+      self.add_detail(prop.span, String::from("member_expression_literals"));
+      self.es_versions.insert(EsVersion::Es5, true);
+      self.features.member_expression_literals = true;
+    }
+    let es5_static = match &*n.obj {
+      Expr::Ident(obj) => {
+        obj.span.ctxt == self.unresolved && ES5_STATICS.contains(&(&*obj.sym, &*prop.sym))
+      }
+      _ => false,
+    };
+    let es5_method = ES5_ARRAY_METHODS.contains(&&*prop.sym);
+    if (es5_static || es5_method) && self.flags.es5_builtins {
+      self.add_detail(n.span, String::from("es5_builtins"));
+      self.es_versions.insert(EsVersion::Es5, true);
+      self.features.es5_builtins = true;
+      // any receiver, e.g. a jQuery collection's `.map`
+      if es5_method && !matches!(n.obj.unwrap_parens(), Expr::Array(..)) {
+        self.mark_last_detail_low_confidence();
+      }
+    }
+  }
+
+  // { a: 1, a: 2 } | { a, get a() {} }
+  fn visit_object_lit(&mut self, n: &ObjectLit) {
+    n.visit_children_with(self);
+    if !self.flags.duplicate_keys {
+      return;
+    }
+    // a getter and a setter of the same name make up one accessor property
+    let mut data = HashSet::new();
+    let mut getters = HashSet::new();
+    let mut setters = HashSet::new();
+    for prop in n.props.iter().filter_map(|prop| prop.as_prop()) {
+      let duplicate = match &**prop {
+        Prop::Getter(GetterProp { key, .. }) => {
+          prop_name_key(key).is_some_and(|key| data.contains(&key) || !getters.insert(key))
+        }
+        Prop::Setter(SetterProp { key, .. }) => {
+          prop_name_key(key).is_some_and(|key| data.contains(&key) || !setters.insert(key))
+        }
+        Prop::Shorthand(ident) => {
+          let key = ident.sym.to_string();
+          getters.contains(&key) || setters.contains(&key) || !data.insert(key)
+        }
+        Prop::KeyValue(KeyValueProp { key, .. }) | Prop::Method(MethodProp { key, .. }) => {
+          prop_name_key(key).is_some_and(|key| {
+            getters.contains(&key) || setters.contains(&key) || !data.insert(key)
+          })
+        }
+        Prop::Assign(..) => false,
+      };
+      if duplicate {
+        self.add_detail(prop.span(), String::from("duplicate_keys"));
+        self.features.duplicate_keys = true;
+        self.es_versions.insert(EsVersion::Es2015, true);
+      }
+    }
+  }
+
+  // /Foo\s+(\d+)/y /(?<year>\d{4})/u
   fn visit_regex(&mut self, n: &Regex) {
-    if n.flags.contains("y") && self.flags.sticky_regex {
-      self.add_detail(n.span, String::from("sticky_regex"));
-      self.features.sticky_regex = true;
-      self.es_versions.insert(EsVersion::Es2015, true);
+    self.check_regex(n.span, &n.exp, &n.flags, true);
+  }
+
+  // new RegExp("(?<year>\\d{4})", "y")
+  fn visit_new_expr(&mut self, n: &NewExpr) {
+    n.visit_children_with(self);
+    if let Some((exp, flags)) = n
+      .args
+      .as_ref()
+      .and_then(|args| regexp_constructor_args(&n.callee, args, self.unresolved))
+    {
+      self.check_regex(n.span, &exp, &flags, false);
+    }
+  }
+
+  // RegExp(pattern, "s")
+  // import("./a.js")
+  fn visit_call_expr(&mut self, n: &CallExpr) {
+    n.visit_children_with(self);
+    match &n.callee {
+      Callee::Expr(callee) => {
+        if let Some((exp, flags)) = regexp_constructor_args(callee, &n.args, self.unresolved) {
+          self.check_regex(n.span, &exp, &flags, false);
+        }
+      }
+      Callee::Import(..) => {
+        if self.flags.dynamic_import {
+          self.add_detail(n.span, String::from("dynamic_import"));
+          self.features.dynamic_import = true;
+          self.es_versions.insert(EsVersion::Es2020, true);
+        }
+        // import("./a.json", { with: { type: "json" } })
+        if let Some(options) = n.args.get(1) {
+          self.check_import_attributes(options.expr.span());
+        }
+      }
+      _ => (),
+    }
+  }
+  // 0b11 0o17 1_000
+  fn visit_number(&mut self, n: &Number) {
+    if let Some(raw) = &n.raw {
+      self.check_numeric_raw(n.span, raw);
+    }
+  }
+
+  // 1n
+  fn visit_big_int(&mut self, n: &BigInt) {
+    if self.flags.big_int {
+      self.add_detail(n.span, String::from("big_int"));
+      self.features.big_int = true;
+      self.es_versions.insert(EsVersion::Es2020, true);
+    }
+    if let Some(raw) = &n.raw {
+      self.check_numeric_raw(n.span, raw);
     }
   }
+
+  // "\u{1F600}"
+  fn visit_str(&mut self, n: &Str) {
+    if let Some(raw) = &n.raw {
+      self.check_unicode_escapes(n.span, raw);
+    }
+  }
+
+  // `\u{1F600}`
+  fn visit_tpl_element(&mut self, n: &TplElement) {
+    self.check_unicode_escapes(n.span, &n.raw);
+  }
+
+  // var \u{61} = 1
+  fn visit_ident(&mut self, n: &Ident) {
+    // escaped identifiers are always longer than their name
+    if (n.span.hi - n.span.lo).0 as usize == n.sym.len() {
+      return;
+    }
+    let source_file = self.source_file.clone();
+    let lo = (n.span.lo - source_file.start_pos).0 as usize;
+    let hi = (n.span.hi - source_file.start_pos).0 as usize;
+    if let Some(raw) = source_file.src.get(lo..hi) {
+      self.check_unicode_escapes(n.span, raw);
+    }
+  }
+
   // template string
   fn visit_tpl(&mut self, n: &Tpl) {
     n.visit_children_with(self);
@@ -301,11 +1231,12 @@ impl Visit for ESC {
   }
 
   // const let
-  fn visit_var_decl_kind(&mut self, n: &VarDeclKind) {
+  fn visit_var_decl(&mut self, n: &VarDecl) {
     n.visit_children_with(self);
-    match n {
+    match n.kind {
       VarDeclKind::Const | VarDeclKind::Let => {
         if self.flags.block_scoping {
+          self.add_detail(n.span, String::from("block_scoping"));
           self.features.block_scoping = true;
           self.es_versions.insert(EsVersion::Es2015, true);
         }
@@ -325,7 +1256,7 @@ impl Visit for ESC {
     }
   }
 
-  // #private
+  // #private() {}
   fn visit_private_method(&mut self, n: &PrivateMethod) {
     n.visit_children_with(self);
     if self.flags.private_methods {
@@ -333,49 +1264,31 @@ impl Visit for ESC {
       self.es_versions.insert(EsVersion::Es2022, true);
       self.features.private_methods = true;
     }
+    if n.is_static {
+      self.check_static_private(n.span);
+    }
   }
 
+  // #private = 1
   fn visit_private_prop(&mut self, n: &PrivateProp) {
     n.visit_children_with(self);
-    if self.flags.private_methods {
-      self.add_detail(n.span, String::from("private_methods"));
+    if self.flags.private_fields {
+      self.add_detail(n.span, String::from("private_fields"));
       self.es_versions.insert(EsVersion::Es2022, true);
-      self.features.private_methods = true;
+      self.features.private_fields = true;
+    }
+    if n.is_static {
+      self.check_static_private(n.span);
     }
   }
 
   // async function a() {}
   fn visit_function(&mut self, n: &Function) {
+    self.function_depth += 1;
     n.visit_children_with(self);
-    // function a({ x, y }) {}
-    if contains_destructuring(&n.params)
-      && !contains_object_rest(&n.params)
-      && self.flags.destructuring
-    {
-      self.add_detail(n.span, String::from("destructuring"));
-      self.features.destructuring = true;
-      self.es_versions.insert(EsVersion::Es2015, true);
-    }
-    // function a({ x, ...rest }) {}
-    if contains_object_rest(&n.params) && self.flags.object_rest_spread {
-      self.add_detail(n.span, String::from("object_rest_spread"));
-      self.features.object_rest_spread = true;
-      self.es_versions.insert(EsVersion::Es2018, true);
-    }
-    for param in &n.params {
-      match param.pat {
-        // function (x=1) {} | function (...args) {}
-        Pat::Assign(..) | Pat::Rest(..) => {
-          if self.flags.parameters {
-            self.add_detail(n.span, String::from("parameters"));
-            self.es_versions.insert(EsVersion::Es2015, true);
-            self.features.parameters = true;
-          }
-          return;
-        }
-        _ => (),
-      }
-    }
+    self.function_depth -= 1;
+    let params: Vec<&Pat> = n.params.iter().map(|param| &param.pat).collect();
+    self.check_params(n.span, &params);
     if n.is_async && self.flags.async_to_generator {
       self.add_detail(n.span, String::from("async_to_generator"));
       self.es_versions.insert(EsVersion::Es2017, true);
@@ -386,11 +1299,22 @@ impl Visit for ESC {
       self.es_versions.insert(EsVersion::Es2015, true);
       self.features.regenerator = true
     }
+    // async function* a() {}
+    if n.is_async && n.is_generator && self.flags.async_generator_functions {
+      self.add_detail(n.span, String::from("async_generator_functions"));
+      self.es_versions.insert(EsVersion::Es2018, true);
+      self.features.async_generator_functions = true
+    }
   }
 
   // const b = async () => {}
   fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+    self.function_depth += 1;
     n.visit_children_with(self);
+    self.function_depth -= 1;
+    // ({ a }, b = 1) => {}
+    let params: Vec<&Pat> = n.params.iter().collect();
+    self.check_params(n.span, &params);
     // async arrow function
     if n.is_async && self.flags.async_to_generator {
       self.add_detail(n.span, String::from("async_to_generator"));
@@ -405,14 +1329,22 @@ impl Visit for ESC {
     }
   }
 
-  // No span info here
   // ??= ||= &&=
-  fn visit_assign_op(&mut self, n: &AssignOp) {
+  fn visit_assign_expr(&mut self, n: &AssignExpr) {
     n.visit_children_with(self);
-    match n {
+    // [a, b] = [b, a]
+    if let PatOrExpr::Pat(pat) = &n.left {
+      self.check_destructuring(n.span, pat);
+    }
+    // a = function () {}
+    if n.op == op!("=") && n.left.as_ident().is_some() {
+      self.check_function_name(&n.right);
+    }
+    match n.op {
       // &&=
       AssignOp::AndAssign | AssignOp::NullishAssign | AssignOp::OrAssign => {
         if self.flags.logical_assignment_operators {
+          self.add_detail(n.span, String::from("logical_assignment_operators"));
           self.features.logical_assignment_operators = true;
           self.es_versions.insert(EsVersion::Es2021, true);
         }
@@ -421,6 +1353,7 @@ impl Visit for ESC {
       // **=
       AssignOp::ExpAssign => {
         if self.flags.exponentiation_operator {
+          self.add_detail(n.span, String::from("exponentiation_operator"));
           self.features.exponentiation_operator = true;
           self.es_versions.insert(EsVersion::Es2016, true);
         }
@@ -443,6 +1376,14 @@ impl Visit for ESC {
         }
         return;
       }
+      // a instanceof B, goes through Symbol.hasInstance since es2015
+      BinaryOp::InstanceOf => {
+        if self.flags.instance_of {
+          self.add_detail(n.span, String::from("instance_of"));
+          self.features.instance_of = true;
+          self.es_versions.insert(EsVersion::Es2015, true);
+        }
+      }
       // **
       BinaryOp::Exp => {
         if self.flags.exponentiation_operator {
@@ -454,25 +1395,31 @@ impl Visit for ESC {
       }
       _ => (),
     };
+    // #x in obj
+    if n.op == op!("in") && n.left.is_private_name() && self.flags.private_property_in_object {
+      self.add_detail(n.span, String::from("private_property_in_object"));
+      self.features.private_property_in_object = true;
+      self.es_versions.insert(EsVersion::Es2022, true);
+    }
     // typeof Symbol() === 'symbol' or 'symbol' === typeof Symbol
     if let Expr::Unary(UnaryExpr {
-      op: op!("typeof"), ..
-    }) = *n.left
+      op: op!("typeof"),
+      arg,
+      ..
+    }) = &*n.left
     {
       if is_symbol_literal(&n.right) && self.flags.typeof_symbol {
-        self.add_detail(n.span, String::from("typeof_symbol"));
-        self.features.typeof_symbol = true;
-        self.es_versions.insert(EsVersion::Es2015, true);
+        self.check_typeof_symbol(n.span, arg);
       }
     }
     if let Expr::Unary(UnaryExpr {
-      op: op!("typeof"), ..
-    }) = *n.right
+      op: op!("typeof"),
+      arg,
+      ..
+    }) = &*n.right
     {
       if is_symbol_literal(&n.left) && self.flags.typeof_symbol {
-        self.add_detail(n.span, String::from("typeof_symbol"));
-        self.features.typeof_symbol = true;
-        self.es_versions.insert(EsVersion::Es2015, true);
+        self.check_typeof_symbol(n.span, arg);
       }
     }
   }
@@ -499,42 +1446,9 @@ impl Visit for ESC {
   }
 
   fn visit_var_declarators(&mut self, n: &[VarDeclarator]) {
-    let span = self.get_real_span_from_range(n[0].span, n[n.len() - 1].span);
-    let loc = self.get_real_loc_from_range(n[0].span, n[n.len() - 1].span);
-    // const { a } = { a: 1 }
-    if contains_destructuring(n) && !contains_object_rest(n) && self.flags.destructuring {
-      self.details.push(Detail {
-        feature: String::from("destructuring"),
-        s: span.0,
-        e: span.1,
-        ls: loc.0,
-        le: loc.1,
-      });
-      self.features.destructuring = true;
-      self.es_versions.insert(EsVersion::Es2015, true);
-    }
-    // const { a, ...rest } = { a: 1 }
-    if contains_object_rest(n) && self.flags.object_rest_spread {
-      self.details.push(Detail {
-        feature: String::from("object_rest_spread"),
-        s: span.0,
-        e: span.1,
-        ls: loc.0,
-        le: loc.1,
-      });
-      self.features.object_rest_spread = true;
-      self.es_versions.insert(EsVersion::Es2018, true);
-    }
-    if contains_object_super(n) && self.flags.object_super {
-      self.details.push(Detail {
-        feature: String::from("object_super"),
-        s: span.0,
-        e: span.1,
-        ls: loc.0,
-        le: loc.1,
-      });
-      self.features.object_super = true;
-      self.es_versions.insert(EsVersion::Es2015, true);
+    // const { a } = { a: 1 } | const { a, ...rest } = { a: 1 }
+    for decl in n {
+      self.check_destructuring(decl.span, &decl.name);
     }
     n.visit_children_with(self);
   }
@@ -552,7 +1466,9 @@ impl Visit for ESC {
   fn visit_catch_clause(&mut self, cc: &CatchClause) {
     cc.visit_children_with(self);
 
-    if cc.param.is_some() {
+    // catch ({ message }) {}
+    if let Some(param) = &cc.param {
+      self.check_destructuring(param.span(), param);
       return;
     }
     if self.flags.optional_catch_binding {
@@ -563,25 +1479,181 @@ impl Visit for ESC {
   }
 }
 
-fn contains_object_super<N>(node: &N) -> bool
-where
-  N: VisitWith<ObjectSuperVisitor> + ?Sized,
-{
-  let mut v = ObjectSuperVisitor { found: false };
-  node.visit_with(&mut v);
-  v.found
+// Pattern and flags of `RegExp(pattern, flags)` when at least one of them is a literal string
+fn regexp_constructor_args(
+  callee: &Expr,
+  args: &[ExprOrSpread],
+  unresolved: SyntaxContext,
+) -> Option<(String, String)> {
+  match callee {
+    Expr::Ident(Ident { sym, span, .. }) if &**sym == "RegExp" && span.ctxt == unresolved => (),
+    _ => return None,
+  }
+  let exp = args.first().and_then(literal_string);
+  let flags = args.get(1).and_then(literal_string);
+  if exp.is_none() && flags.is_none() {
+    return None;
+  }
+  Some((exp.unwrap_or_default(), flags.unwrap_or_default()))
 }
 
-#[derive(Default)]
-struct ObjectSuperVisitor {
-  found: bool,
+fn literal_string(arg: &ExprOrSpread) -> Option<String> {
+  if arg.spread.is_some() {
+    return None;
+  }
+  match &*arg.expr {
+    Expr::Lit(Lit::Str(Str { value, .. })) => Some(value.to_string()),
+    // `(?<year>\\d{4})` without expressions
+    Expr::Tpl(Tpl { exprs, quasis, .. }) if exprs.is_empty() => quasis
+      .first()
+      .and_then(|quasi| quasi.cooked.as_ref())
+      .map(|cooked| cooked.to_string()),
+    _ => None,
+  }
 }
 
-impl Visit for ObjectSuperVisitor {
-  noop_visit_type!();
+// Byte ranges of `\u{...}` escapes in raw source text
+fn code_point_escapes(raw: &str) -> Vec<(usize, usize)> {
+  let bytes = raw.as_bytes();
+  let mut ranges = vec![];
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] != b'\\' {
+      i += 1;
+      continue;
+    }
+    if bytes.get(i + 1) == Some(&b'u') && bytes.get(i + 2) == Some(&b'{') {
+      if let Some(end) = raw[i..].find('}') {
+        ranges.push((i, i + end + 1));
+        i += end + 1;
+        continue;
+      }
+    }
+    // skip the escaped char, e.g. "\\u{41}" is not an escape
+    i += 2;
+  }
+  ranges
+}
+
+// Syntax that has to be supported natively by the targets
+fn is_transformable(feature: &str) -> bool {
+  !matches!(
+    feature,
+    "big_int"
+      | "property_mutators"
+      | "lookbehind_regex"
+      | "has_indices_regex"
+      | "duplicate_named_groups_regex"
+      | "top_level_await"
+      | "hashbang"
+  )
+}
+
+// Keywords and es3 future reserved words, only valid as property names since es5
+const ES3_RESERVED: &[&str] = &[
+  "break",
+  "case",
+  "catch",
+  "class",
+  "const",
+  "continue",
+  "debugger",
+  "default",
+  "delete",
+  "do",
+  "else",
+  "enum",
+  "export",
+  "extends",
+  "false",
+  "finally",
+  "for",
+  "function",
+  "if",
+  "import",
+  "in",
+  "instanceof",
+  "new",
+  "null",
+  "return",
+  "super",
+  "switch",
+  "this",
+  "throw",
+  "true",
+  "try",
+  "typeof",
+  "var",
+  "void",
+  "while",
+  "with",
+  "abstract",
+  "boolean",
+  "byte",
+  "char",
+  "double",
+  "final",
+  "float",
+  "goto",
+  "implements",
+  "int",
+  "interface",
+  "long",
+  "native",
+  "package",
+  "private",
+  "protected",
+  "public",
+  "short",
+  "static",
+  "synchronized",
+  "throws",
+  "transient",
+  "volatile",
+];
+
+// Array and Object statics added in es5
+const ES5_STATICS: &[(&str, &str)] = &[
+  ("Array", "isArray"),
+  ("Object", "keys"),
+  ("Object", "create"),
+  ("Object", "defineProperty"),
+  ("Object", "defineProperties"),
+  ("Object", "getPrototypeOf"),
+  ("Object", "getOwnPropertyNames"),
+  ("Object", "getOwnPropertyDescriptor"),
+  ("Object", "freeze"),
+  ("Object", "isFrozen"),
+  ("Object", "seal"),
+  ("Object", "isSealed"),
+  ("Object", "preventExtensions"),
+  ("Object", "isExtensible"),
+];
 
-  fn visit_super_prop_expr(&mut self, _n: &SuperPropExpr) {
-    self.found = true;
+// Array.prototype methods added in es5, matched by name on any receiver, so `$(el).map(fn)`
+// is reported too, as low confidence. indexOf and lastIndexOf are left out, strings have them
+// since es3
+const ES5_ARRAY_METHODS: &[&str] = &[
+  "forEach",
+  "map",
+  "filter",
+  "reduce",
+  "reduceRight",
+  "every",
+  "some",
+];
+
+fn is_es3_reserved(sym: &str) -> bool {
+  ES3_RESERVED.contains(&sym)
+}
+
+// Static name of an object literal key, `1` and '1' are the same key
+fn prop_name_key(key: &PropName) -> Option<String> {
+  match key {
+    PropName::Ident(ident) => Some(ident.sym.to_string()),
+    PropName::Str(s) => Some(s.value.to_string()),
+    PropName::Num(n) => Some(n.value.to_string()),
+    PropName::Computed(..) | PropName::BigInt(..) => None,
   }
 }
 
@@ -592,6 +1664,19 @@ fn is_symbol_literal(e: &Expr) -> bool {
   }
 }
 
+// `Symbol` in `typeof Symbol`, `typeof Symbol()` or `typeof Symbol.iterator`
+fn symbol_ident(e: &Expr) -> Option<&Ident> {
+  match e.unwrap_parens() {
+    Expr::Ident(ident) if &*ident.sym == "Symbol" => Some(ident),
+    Expr::Call(CallExpr {
+      callee: Callee::Expr(callee),
+      ..
+    }) => symbol_ident(callee),
+    Expr::Member(MemberExpr { obj, .. }) => symbol_ident(obj),
+    _ => None,
+  }
+}
+
 fn contains_destructuring<N>(node: &N) -> bool
 where
   N: VisitWith<DestructuringVisitor> + ?Sized,
@@ -611,11 +1696,14 @@ impl Visit for DestructuringVisitor {
 
   fn visit_pat(&mut self, n: &Pat) {
     n.visit_children_with(self);
-    match n {
-      Pat::Ident(..) => (),
-      _ => self.found = true,
+    // defaults and rest elements are handled by `parameters`
+    if let Pat::Array(..) | Pat::Object(..) = n {
+      self.found = true;
     }
   }
+
+  // default values and computed keys, a function there has its own params
+  fn visit_expr(&mut self, _: &Expr) {}
 }
 
 fn contains_object_rest<N>(node: &N) -> bool
@@ -641,4 +1729,6 @@ impl Visit for RestVisitor {
       _ => prop.visit_children_with(self),
     }
   }
+
+  fn visit_expr(&mut self, _: &Expr) {}
 }
@@ -0,0 +1,224 @@
+use swc_core::common::{Span, Spanned};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::web_apis::GLOBAL_INSTANCES;
+
+// Objects a global can be read from, e.g. window.ResizeObserver
+const GLOBAL_OBJECTS: &[&str] = &["global", "globalThis", "self", "window"];
+
+// Code only reached when the runtime has the APIs in `names`, e.g. the body of
+// `if (typeof Symbol !== 'undefined') {}`
+#[derive(Debug, Clone)]
+pub struct Guard {
+  pub span: Span,
+  // API paths the condition checked, e.g. Symbol or Array.prototype.at
+  pub names: Vec<String>,
+}
+
+impl Guard {
+  // whether a finding of the API `name` at `span` is behind this guard. Only the checked path
+  // itself or its global counts, `'at' in Array.prototype` doesn't guard String.prototype.at
+  pub fn covers(&self, span: Span, name: &str) -> bool {
+    if span.lo < self.span.lo || self.span.hi < span.hi {
+      return false;
+    }
+    // Navigator.clipboard is read as navigator.clipboard
+    let instance_path = GLOBAL_INSTANCES.iter().find_map(|(interface, instance)| {
+      let member = name.strip_prefix(interface)?.strip_prefix('.')?;
+      Some(format!("{}.{}", instance, member))
+    });
+    self.names.iter().any(|guard| {
+      name == guard
+        || name.split('.').next() == Some(guard.as_str())
+        || instance_path.as_deref() == Some(guard.as_str())
+        || name.strip_prefix("node:") == Some(guard.as_str())
+    })
+  }
+}
+
+// Every guarded region of `program`
+pub fn analyze_guards(program: &Program) -> Vec<Guard> {
+  let mut visitor = GuardVisitor::default();
+  program.visit_with(&mut visitor);
+  visitor.guards
+}
+
+#[derive(Default)]
+struct GuardVisitor {
+  guards: Vec<Guard>,
+}
+
+impl GuardVisitor {
+  fn add(&mut self, span: Span, names: Vec<String>) {
+    if !names.is_empty() {
+      self.guards.push(Guard { span, names });
+    }
+  }
+
+  // `Array.prototype.at` in `if (Array.prototype.at)` is only read, never called
+  fn add_probes(&mut self, test: &Expr) {
+    match test {
+      Expr::Paren(ParenExpr { expr: arg, .. })
+      | Expr::Unary(UnaryExpr {
+        op: op!("!"), arg, ..
+      }) => self.add_probes(arg),
+      Expr::Bin(BinExpr {
+        op: op!("&&") | op!("||"),
+        left,
+        right,
+        ..
+      }) => {
+        self.add_probes(left);
+        self.add_probes(right);
+      }
+      Expr::Member(..) | Expr::Ident(..) => self.add(test.span(), api_names(test)),
+      _ => (),
+    }
+  }
+
+  // `test ? cons : alt`, or an if statement
+  fn add_branches(&mut self, test: &Expr, cons: Span, alt: Option<Span>) {
+    self.add_probes(test);
+    self.add(cons, checked_names(test, true));
+    if let Some(alt) = alt {
+      self.add(alt, checked_names(test, false));
+    }
+  }
+}
+
+impl Visit for GuardVisitor {
+  noop_visit_type!();
+
+  // if (typeof Symbol !== 'undefined' && Symbol.asyncIterator) {}
+  fn visit_if_stmt(&mut self, n: &IfStmt) {
+    n.visit_children_with(self);
+    self.add_branches(&n.test, n.cons.span(), n.alt.as_ref().map(|alt| alt.span()));
+  }
+
+  // typeof fetch === 'function' ? fetch(url) : xhr(url)
+  fn visit_cond_expr(&mut self, n: &CondExpr) {
+    n.visit_children_with(self);
+    self.add_branches(&n.test, n.cons.span(), Some(n.alt.span()));
+  }
+
+  // typeof Symbol !== 'undefined' && Symbol.iterator | !Array.prototype.at || [].at(-1)
+  fn visit_bin_expr(&mut self, n: &BinExpr) {
+    n.visit_children_with(self);
+    match n.op {
+      op!("&&") => self.add(n.right.span(), checked_names(&n.left, true)),
+      op!("||") => self.add(n.right.span(), checked_names(&n.left, false)),
+      _ => (),
+    }
+  }
+
+  // typeof structuredClone, never throws
+  fn visit_unary_expr(&mut self, n: &UnaryExpr) {
+    n.visit_children_with(self);
+    if n.op == op!("typeof") {
+      self.add(n.arg.span(), api_names(&n.arg));
+    }
+  }
+}
+
+// APIs `test` checks for, when it evaluates to `when`. `!` and parentheses are unwrapped, `&&`
+// when true and `||` when false add up the checks of both sides
+fn checked_names(test: &Expr, when: bool) -> Vec<String> {
+  match test {
+    Expr::Paren(ParenExpr { expr, .. }) => checked_names(expr, when),
+    Expr::Unary(UnaryExpr {
+      op: op!("!"), arg, ..
+    }) => checked_names(arg, !when),
+    Expr::Bin(BinExpr {
+      op: op!("&&"),
+      left,
+      right,
+      ..
+    }) if when => [checked_names(left, when), checked_names(right, when)].concat(),
+    Expr::Bin(BinExpr {
+      op: op!("||"),
+      left,
+      right,
+      ..
+    }) if !when => [checked_names(left, when), checked_names(right, when)].concat(),
+    // typeof Symbol !== 'undefined' | typeof fetch === 'function'
+    Expr::Bin(BinExpr {
+      op, left, right, ..
+    }) if matches!(op, op!("===") | op!("==") | op!("!==") | op!("!=")) => {
+      let (typeof_arg, value) = match (&**left, &**right) {
+        (
+          Expr::Unary(UnaryExpr {
+            op: op!("typeof"),
+            arg,
+            ..
+          }),
+          Expr::Lit(Lit::Str(s)),
+        )
+        | (
+          Expr::Lit(Lit::Str(s)),
+          Expr::Unary(UnaryExpr {
+            op: op!("typeof"),
+            arg,
+            ..
+          }),
+        ) => (arg, &*s.value),
+        _ => return vec![],
+      };
+      let equal = matches!(op, op!("===") | op!("=="));
+      // `=== 'undefined'` is true when missing, any other type when present
+      let present = equal != (value == "undefined");
+      if present == when {
+        api_names(typeof_arg)
+      } else {
+        vec![]
+      }
+    }
+    // 'at' in Array.prototype | 'ResizeObserver' in window
+    Expr::Bin(BinExpr {
+      op: op!("in"),
+      left,
+      right,
+      ..
+    }) if when => match &**left {
+      Expr::Lit(Lit::Str(s)) => {
+        let prop = s.value.to_string();
+        match member_path(right) {
+          Some(obj) if !GLOBAL_OBJECTS.contains(&obj.as_str()) => {
+            vec![format!("{}.{}", obj, prop)]
+          }
+          _ => vec![prop],
+        }
+      }
+      _ => vec![],
+    },
+    // Symbol.asyncIterator | window.fetch
+    Expr::Member(..) | Expr::Ident(..) if when => api_names(test),
+    _ => vec![],
+  }
+}
+
+// `Array.prototype.at` for a member chain of identifiers, `window.` is dropped
+fn api_names(expr: &Expr) -> Vec<String> {
+  let Some(path) = member_path(expr) else {
+    return vec![];
+  };
+  let path = GLOBAL_OBJECTS
+    .iter()
+    .find_map(|global| path.strip_prefix(&format!("{}.", global)))
+    .map(String::from)
+    .unwrap_or(path);
+  vec![path]
+}
+
+fn member_path(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Ident(ident) => Some(ident.sym.to_string()),
+    Expr::Member(MemberExpr {
+      obj,
+      prop: MemberProp::Ident(prop),
+      ..
+    }) => Some(format!("{}.{}", member_path(obj)?, prop.sym)),
+    Expr::Paren(ParenExpr { expr, .. }) => member_path(expr),
+    _ => None,
+  }
+}
@@ -1,25 +1,88 @@
+mod bugfixes;
+mod builtins;
+mod config;
+mod data;
 mod esc;
+mod guards;
+mod node;
+mod regexp;
+mod runtime;
+mod tokens;
+mod typescript;
+mod web_apis;
 #[macro_use]
 extern crate napi_derive;
 
 use anyhow::{anyhow, Context};
-use esc::{compat, Detail, FeaturesFlag, Line};
-use preset_env_base::query::Query;
+use esc::{compat, DataConfig, Detail, FeaturesFlag, Line, DATA_FEATURES};
+use napi::bindgen_prelude::Either3;
+use preset_env_base::query::{targets_to_versions, Query};
+use preset_env_base::version::Version;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
+use std::path::Path;
 use std::panic::{catch_unwind, AssertUnwindSafe};
-use swc_compiler_base::{parse_js, IsModule};
 use swc_core::common::comments::SingleThreadedComments;
 use swc_core::common::errors::Handler;
-use swc_core::common::{sync::Lrc, FileName, SourceMap, GLOBALS};
-use swc_core::ecma::ast::EsVersion;
-use swc_core::ecma::visit::VisitWith;
-use swc_ecma_parser::Syntax;
-use swc_ecma_preset_env::{Config, Targets};
+use swc_core::common::{sync::Lrc, FileName, Mark, SourceFile, SourceMap, Span, Spanned, GLOBALS};
+use swc_core::ecma::ast::{EsVersion, Program};
+use swc_core::ecma::transforms::base::resolver;
+use swc_core::ecma::visit::{VisitMutWith, VisitWith};
+use swc_ecma_parser::error::SyntaxError;
+use swc_ecma_parser::{parse_file_as_module, EsConfig, Syntax, TsConfig};
+use bugfixes::analyze_bugfixes;
+use builtins::{analyze_builtins, core_js_modules};
+use config::{load_browserslist, load_tsconfig_target};
+use guards::analyze_guards;
+use node::{analyze_node_apis, min_node_version};
+use tokens::analyze_tokens;
+use typescript::strip_types;
+use web_apis::analyze_web_apis;
+use swc_ecma_preset_env::{BrowserData, Config, Feature, FeatureOrModule, Targets, Versions};
 use swc_error_reporters::handler::{try_with_handler, HandlerOpts};
 use sourcemap::SourceMap as RawSourceMap;
 
+// Same as swc's `parse_js` for a module, except for the `v` regex flag. The parser rejects
+// `/[\p{L}--[a-z]]/v` as unknown flags but still builds the literal
+fn parse_module(
+  fm: &SourceFile,
+  handler: &Handler,
+  syntax: Syntax,
+  comments: &SingleThreadedComments,
+) -> Result<Program, anyhow::Error> {
+  let mut errors = vec![];
+  let program = parse_file_as_module(fm, syntax, EsVersion::EsNext, Some(comments), &mut errors);
+  let mut error = false;
+  for e in errors {
+    if matches!(e.kind(), SyntaxError::UnknownRegExpFlags) && is_unicode_sets_regex(fm, e.span()) {
+      continue;
+    }
+    e.into_diagnostic(handler).emit();
+    error = true;
+  }
+  let program = program.map(Program::Module).map_err(|e| {
+    e.into_diagnostic(handler).emit();
+    anyhow!("Syntax Error")
+  })?;
+  if error {
+    return Err(anyhow!("Syntax Error"));
+  }
+  Ok(program)
+}
+
+// `/a/v`, `v` is the only flag the parser doesn't know. `u` and `v` together are still an error
+fn is_unicode_sets_regex(fm: &SourceFile, span: Span) -> bool {
+  let lo = (span.lo - fm.start_pos).0 as usize;
+  let hi = (span.hi - fm.start_pos).0 as usize;
+  let flags = fm
+    .src
+    .get(lo..hi)
+    .and_then(|regex| regex.rsplit('/').next())
+    .unwrap_or_default();
+  flags.contains('v') && !flags.contains('u') && flags.chars().all(|flag| "dgimsvy".contains(flag))
+}
+
 fn try_with<F, Ret>(cm: Lrc<SourceMap>, skip_filename: bool, op: F) -> Result<Ret, anyhow::Error>
 where
   F: FnOnce(&Handler) -> Result<Ret, anyhow::Error>,
@@ -51,34 +114,216 @@ where
   })
 }
 
-fn parse_target(target: Option<String>) -> EsVersion {
-  if target.is_none() {
-    return EsVersion::EsNext;
+fn parse_target(target: Option<String>) -> Result<esc::EsVersion, napi::Error> {
+  let Some(target) = target else {
+    return Ok(esc::EsVersion::EsNext);
+  };
+  let es_version = match target.to_lowercase().as_str() {
+    "esnext" => esc::EsVersion::EsNext,
+    "es2025" => esc::EsVersion::Es2025,
+    "es2024" => esc::EsVersion::Es2024,
+    "es2023" => esc::EsVersion::Es2023,
+    "es2022" => esc::EsVersion::Es2022,
+    "es2021" => esc::EsVersion::Es2021,
+    "es2020" => esc::EsVersion::Es2020,
+    "es2019" => esc::EsVersion::Es2019,
+    "es2018" => esc::EsVersion::Es2018,
+    "es2017" => esc::EsVersion::Es2017,
+    "es2016" => esc::EsVersion::Es2016,
+    "es2015" | "es6" => esc::EsVersion::Es2015,
+    "es5" => esc::EsVersion::Es5,
+    "es3" => esc::EsVersion::Es3,
+    _ => return Err(napi::Error::from_reason(format!("unknown target: {}", target))),
+  };
+  Ok(es_version)
+}
+
+// a query, a list of queries or versions per browser, e.g. { chrome: "87", safari: "13.1" }
+// Returns the versions and the queries they came from
+fn parse_browserslist(
+  browserslist: Either3<String, Vec<String>, HashMap<String, String>>,
+) -> Result<(Versions, Option<Vec<String>>), napi::Error> {
+  let query = match browserslist {
+    Either3::A(query) => Query::Single(query),
+    Either3::B(queries) if queries.is_empty() => {
+      return Err(invalid_browserslist(String::from("no browserslist queries")));
+    }
+    Either3::B(queries) => Query::Multiple(queries),
+    Either3::C(versions) if versions.is_empty() => {
+      return Err(invalid_browserslist(String::from("no browser versions")));
+    }
+    Either3::C(versions) => {
+      // mobile browsers are checked as their desktop engine, like queries do. The lower version
+      // wins when both are given
+      let mut desktop: HashMap<&str, String> = HashMap::new();
+      for (browser, version) in &versions {
+        let browser = desktop_browser(browser);
+        let lower = desktop.get(browser).map_or(true, |other| {
+          version.parse::<Version>().ok() < other.parse::<Version>().ok()
+        });
+        if lower {
+          desktop.insert(browser, version.clone());
+        }
+      }
+      let versions = serde_json::to_value(desktop)
+        .and_then(serde_json::from_value)
+        .map_err(|err| invalid_browserslist(err.to_string()))?;
+      return Ok((versions, None));
+    }
+  };
+  let queries = match &query {
+    Query::Single(query) => vec![query.clone()],
+    Query::Multiple(queries) => queries.clone(),
+  };
+  let versions = targets_to_versions(Some(Targets::Query(query)))
+    .map_err(|err| invalid_browserslist(err.root_cause().to_string()))?;
+  Ok((versions, Some(queries)))
+}
+
+// same names as preset-env gives the browsers of a query
+fn desktop_browser(browser: &str) -> &str {
+  match browser {
+    "and_chr" => "chrome",
+    "and_ff" => "firefox",
+    "ie_mob" => "ie",
+    "ios_saf" => "ios",
+    "op_mob" => "opera",
+    _ => browser,
   }
-  let es_version = match target.unwrap().as_str() {
-    "esnext" => EsVersion::EsNext,
-    "es2022" => EsVersion::Es2022,
-    "es2021" => EsVersion::Es2021,
-    "es2020" => EsVersion::Es2020,
-    "es2019" => EsVersion::Es2019,
-    "es2018" => EsVersion::Es2018,
-    "es2017" => EsVersion::Es2017,
-    "es2016" => EsVersion::Es2016,
-    "es2015" => EsVersion::Es2015,
-    "es5" => EsVersion::Es5,
-    "es3" => EsVersion::Es3,
-    _ => EsVersion::EsNext,
+}
+
+fn invalid_browserslist(reason: String) -> napi::Error {
+  napi::Error::from_reason(format!("invalid browserslist: {}", reason))
+}
+
+// preset-env `include`/`exclude`, plugin names like "transform-parameters" or
+// "@babel/plugin-transform-parameters", core-js modules like "es.array.at" and
+// features without a plugin like "bigInt"
+fn parse_feature_or_modules(
+  option: &str,
+  names: Option<Vec<String>>,
+) -> Result<(Vec<FeatureOrModule>, Vec<&'static str>), napi::Error> {
+  let mut features = vec![];
+  let mut data_features = vec![];
+  for name in names.unwrap_or_default() {
+    let plugin = name
+      .trim_start_matches("@babel/plugin-")
+      .trim_start_matches("babel-plugin-");
+    if let Ok(feature) = plugin.parse::<Feature>() {
+      features.push(FeatureOrModule::Feature(feature));
+    } else if let Some(feature) = data_feature(&name) {
+      data_features.push(feature);
+    } else if core_js_modules().contains(&name) {
+      features.push(FeatureOrModule::CoreJsModule(name));
+    } else {
+      return Err(napi::Error::from_reason(format!("unknown {}: {}", option, name)));
+    }
+  }
+  Ok((features, data_features))
+}
+
+// "bigInt" like the `features` output to "big_int"
+fn data_feature(name: &str) -> Option<&'static str> {
+  let snake_case = name.chars().fold(String::new(), |mut snake_case, ch| {
+    if ch.is_ascii_uppercase() {
+      snake_case.push('_');
+    }
+    snake_case.push(ch.to_ascii_lowercase());
+    snake_case
+  });
+  DATA_FEATURES.iter().copied().find(|feature| *feature == snake_case)
+}
+
+fn same_feature_or_module(a: &FeatureOrModule, b: &FeatureOrModule) -> bool {
+  match (a, b) {
+    (FeatureOrModule::Feature(a), FeatureOrModule::Feature(b)) => a == b,
+    (FeatureOrModule::CoreJsModule(a), FeatureOrModule::CoreJsModule(b)) => a == b,
+    _ => false,
+  }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SyntaxOptions {
+  // "ecmascript" or "typescript"
+  pub syntax: Option<String>,
+  pub jsx: Option<bool>,
+  pub tsx: Option<bool>,
+  // defaults to true
+  pub decorators: Option<bool>,
+}
+
+// `options`, or the syntax of the `filename` extension. .ts, .mts and .cts are TypeScript, .tsx
+// with JSX, .jsx is JavaScript with JSX
+fn parse_syntax(filename: &str, options: Option<SyntaxOptions>) -> Result<Syntax, napi::Error> {
+  let extension = Path::new(filename)
+    .extension()
+    .and_then(|extension| extension.to_str())
+    .unwrap_or_default();
+  let options = options.unwrap_or_else(|| SyntaxOptions {
+    syntax: Some(
+      match extension {
+        "ts" | "mts" | "cts" | "tsx" => "typescript",
+        _ => "ecmascript",
+      }
+      .to_string(),
+    ),
+    jsx: Some(extension == "jsx"),
+    tsx: Some(extension == "tsx"),
+    decorators: None,
+  });
+  let decorators = options.decorators.unwrap_or(true);
+  let syntax = match options.syntax.as_deref().unwrap_or("ecmascript") {
+    "ecmascript" => Syntax::Es(EsConfig {
+      jsx: options.jsx.unwrap_or(false),
+      import_attributes: true,
+      decorators,
+      auto_accessors: true,
+      explicit_resource_management: true,
+      ..Default::default()
+    }),
+    "typescript" => Syntax::Typescript(TsConfig {
+      tsx: options.tsx.unwrap_or(false),
+      decorators,
+      dts: filename.ends_with(".d.ts"),
+      ..Default::default()
+    }),
+    syntax => return Err(napi::Error::from_reason(format!("unknown syntax: {}", syntax))),
   };
-  es_version
+  Ok(syntax)
 }
 
 #[napi(object)]
 #[derive(Debug, Clone)]
 pub struct ParseOptions {
   pub target: Option<String>,
-  pub browserslist: String,
+  // tsconfig.json whose `compilerOptions.target` is used when `target` is missing
+  pub tsconfig: Option<String>,
+  // parser syntax, picked from the `filename` extension when missing
+  pub syntax: Option<SyntaxOptions>,
+  // resolved from the browserslist config of `path` when missing
+  pub browserslist: Option<Either3<String, Vec<String>, HashMap<String, String>>>,
+  // file or directory to look up .browserslistrc or package.json `browserslist` from, defaults to
+  // `filename`
+  pub path: Option<String>,
+  // browserslist environment, e.g. "legacy". Defaults to BROWSERSLIST_ENV, NODE_ENV or "production"
+  pub env: Option<String>,
   pub filename: String,
   pub code: String,
+  // skip code injected by bundlers instead of tagging it with `origin: "runtime"`
+  pub skip_runtime: Option<bool>,
+  // also check DOM and web platform APIs, e.g. ResizeObserver
+  pub web_apis: Option<bool>,
+  // node version range, e.g. ">=14.17" from package.json engines. Replaces `browserslist`
+  pub node: Option<String>,
+  // preset-env `bugfixes`, report engine bugs instead of whole features. Defaults to true
+  pub bugfixes: Option<bool>,
+  // preset-env `include`, features and core-js modules reported even if the targets support them
+  pub include: Option<Vec<String>>,
+  // preset-env `exclude`, features and core-js modules never reported
+  pub exclude: Option<Vec<String>>,
+  // preset-env `forceAllTransforms`, report every feature whatever the targets
+  pub force_all_transforms: Option<bool>,
 }
 
 #[napi(object)]
@@ -87,6 +332,8 @@ pub struct DetectResult {
   pub features: FeaturesFlag,
   pub es_versions: HashMap<String, bool>,
   pub details: Vec<Detail>,
+  // browserslist queries the code was checked against, None for versions or `node`
+  pub query: Option<Vec<String>>,
 }
 
 #[napi]
@@ -95,39 +342,123 @@ pub fn detect(options: ParseOptions) -> Result<DetectResult, napi::Error> {
     filename,
     code,
     browserslist,
+    path,
+    env,
     target,
+    tsconfig,
+    syntax,
+    skip_runtime,
+    web_apis,
+    node,
+    bugfixes,
+    include,
+    exclude,
+    force_all_transforms,
   } = options;
+  let syntax = parse_syntax(&filename, syntax)?;
+  // browserslist config is looked up from the file by default
+  let path = path.unwrap_or_else(|| filename.clone());
   let cm: Lrc<SourceMap> = Default::default();
   let fm = cm.new_source_file(FileName::Custom(filename.into()), code.clone().into());
 
-  let env_targets: Targets = Targets::Query(Query::Single(browserslist));
-  let es_version = parse_target(target);
+  let node_version = match node {
+    Some(range) => Some(
+      min_node_version(&range)
+        .ok_or_else(|| napi::Error::from_reason(format!("invalid node range: {}", range)))?,
+    ),
+    None => None,
+  };
+  let (env_targets, query) = match (node_version, browserslist) {
+    (Some(version), _) => (
+      BrowserData {
+        node: Some(version),
+        ..Default::default()
+      },
+      None,
+    ),
+    (None, Some(browserslist)) => parse_browserslist(browserslist)?,
+    (None, None) => {
+      let queries = load_browserslist(&path, env.as_deref())
+        .map_err(|err| invalid_browserslist(format!("{:#}", err)))?;
+      parse_browserslist(Either3::B(queries))?
+    }
+  };
+  let target = match (target, tsconfig) {
+    (None, Some(tsconfig)) => load_tsconfig_target(Path::new(&tsconfig))
+      .map_err(|err| napi::Error::from_reason(format!("invalid tsconfig: {:#}", err)))?,
+    (target, _) => target,
+  };
+  let es_version = parse_target(target)?;
+  let (include, include_data) = parse_feature_or_modules("include", include)?;
+  let (exclude, exclude_data) = parse_feature_or_modules("exclude", exclude)?;
+  if let Some(both) = include
+    .iter()
+    .find(|a| exclude.iter().any(|b| same_feature_or_module(a, b)))
+  {
+    return Err(napi::Error::from_reason(format!(
+      "{} is in both include and exclude",
+      match both {
+        FeatureOrModule::Feature(feature) => feature.as_str(),
+        FeatureOrModule::CoreJsModule(module) => module.as_str(),
+      }
+    )));
+  }
+  if let Some(both) = include_data.iter().find(|a| exclude_data.contains(a)) {
+    return Err(napi::Error::from_reason(format!(
+      "{} is in both include and exclude",
+      both
+    )));
+  }
 
   try_with(cm.clone(), false, |handler| {
     let comments = SingleThreadedComments::default();
-    let module = parse_js(
-      cm.clone(),
-      fm.clone(),
-      &handler,
-      EsVersion::EsNext,
-      Syntax::Es(Default::default()),
-      IsModule::Bool(true),
-      Some(&comments),
-    )
-    .context("failed to parse code")?;
+    let mut module = parse_module(&fm, handler, syntax, &comments)
+      .context("failed to parse code")?;
+    // types never reach the output, e.g. interfaces, `declare`s and `: string`
+    let type_spans = strip_types(&mut module);
+    // globals keep `unresolved_mark`, local bindings like a bundled Promise helper don't
+    let unresolved_mark = Mark::new();
+    module.visit_mut_with(&mut resolver(unresolved_mark, Mark::new(), syntax.typescript()));
     let mut esc = compat(
       es_version,
       cm,
-      fm,
+      fm.clone(),
       Config {
-        targets: Some(env_targets),
+        targets: Some(Targets::Versions(env_targets)),
         mode: None,
         // https://github.com/babel/babel/issues/16254
-        bugfixes: true,
+        bugfixes: bugfixes.unwrap_or(true),
+        include,
+        exclude,
+        force_all_transforms: force_all_transforms.unwrap_or(false),
         ..Default::default()
       },
-    );
+      DataConfig {
+        include: include_data,
+        exclude: exclude_data,
+      },
+      skip_runtime.unwrap_or(false),
+      unresolved_mark,
+    )?;
+    esc.set_guards(analyze_guards(&module));
     module.visit_with(&mut esc);
+    // trailing commas, hashbang, etc. never reach the AST
+    esc.check_tokens(
+      analyze_tokens(&fm, syntax, &module)
+        .into_iter()
+        .filter(|token| !type_spans.iter().any(|span| span.contains(token.span)))
+        .collect(),
+    );
+    esc.check_bugfixes(analyze_bugfixes(&module));
+    if node_version.is_some() {
+      // node versions for built-ins and globals too
+      esc.check_node_apis(analyze_node_apis(&module, unresolved_mark));
+    } else {
+      esc.check_builtins(analyze_builtins(&module, unresolved_mark));
+      if web_apis.unwrap_or(false) {
+        esc.check_web_apis(analyze_web_apis(&module, unresolved_mark));
+      }
+    }
     Ok(DetectResult {
       features: esc.features,
       es_versions: esc
@@ -136,6 +467,7 @@ pub fn detect(options: ParseOptions) -> Result<DetectResult, napi::Error> {
         .map(|(key, value)| (format!("{:?}", key), value))
         .collect::<std::collections::HashMap<String, bool>>(),
       details: esc.details,
+      query,
     })
   })
   .map_err(|err| napi::Error::from_reason(format!("{:?}", err)))
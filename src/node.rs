@@ -0,0 +1,627 @@
+use std::collections::HashMap;
+
+use preset_env_base::version::Version;
+use swc_core::common::{Mark, Span, SyntaxContext};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{noop_visit_type, Visit, VisitWith};
+
+// How a Node.js API shows up in code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Usage<'a> {
+  // import test from "node:test" | require("fs/promises")
+  Module(&'a str),
+  // import { cp } from "fs/promises" | fs.promises.cp()
+  ModuleMember(&'a str, &'a str),
+  // structuredClone(a)
+  Global(&'a str),
+  // AbortSignal.timeout(100)
+  Static(&'a str, &'a str),
+  // a.findLast(cb)
+  Instance(&'a str),
+}
+
+// (API, usage, first Node.js version shipping it unflagged)
+const NODE_APIS: &[(&str, Usage<'static>, &str)] = &[
+  // modules
+  ("node:test", Usage::Module("test"), "18.0.0"),
+  ("node:sqlite", Usage::Module("sqlite"), "22.5.0"),
+  ("fs/promises", Usage::Module("fs/promises"), "14.0.0"),
+  ("dns/promises", Usage::Module("dns/promises"), "15.0.0"),
+  (
+    "stream/promises",
+    Usage::Module("stream/promises"),
+    "15.0.0",
+  ),
+  (
+    "timers/promises",
+    Usage::Module("timers/promises"),
+    "15.0.0",
+  ),
+  (
+    "readline/promises",
+    Usage::Module("readline/promises"),
+    "17.0.0",
+  ),
+  ("assert/strict", Usage::Module("assert/strict"), "15.0.0"),
+  ("path/posix", Usage::Module("path/posix"), "15.3.0"),
+  ("path/win32", Usage::Module("path/win32"), "15.3.0"),
+  ("util/types", Usage::Module("util/types"), "15.3.0"),
+  ("stream/web", Usage::Module("stream/web"), "16.5.0"),
+  (
+    "stream/consumers",
+    Usage::Module("stream/consumers"),
+    "16.7.0",
+  ),
+  (
+    "diagnostics_channel",
+    Usage::Module("diagnostics_channel"),
+    "15.1.0",
+  ),
+  ("worker_threads", Usage::Module("worker_threads"), "12.0.0"),
+  ("perf_hooks", Usage::Module("perf_hooks"), "8.5.0"),
+  ("http2", Usage::Module("http2"), "8.4.0"),
+  ("trace_events", Usage::Module("trace_events"), "10.0.0"),
+  // module members
+  ("fs.cp", Usage::ModuleMember("fs", "cp"), "16.7.0"),
+  ("fs.cpSync", Usage::ModuleMember("fs", "cpSync"), "16.7.0"),
+  (
+    "fs.promises.cp",
+    Usage::ModuleMember("fs/promises", "cp"),
+    "16.7.0",
+  ),
+  ("fs.rm", Usage::ModuleMember("fs", "rm"), "14.14.0"),
+  ("fs.rmSync", Usage::ModuleMember("fs", "rmSync"), "14.14.0"),
+  (
+    "fs.promises.rm",
+    Usage::ModuleMember("fs/promises", "rm"),
+    "14.14.0",
+  ),
+  (
+    "fs.opendir",
+    Usage::ModuleMember("fs", "opendir"),
+    "12.12.0",
+  ),
+  (
+    "fs.promises.opendir",
+    Usage::ModuleMember("fs/promises", "opendir"),
+    "12.12.0",
+  ),
+  ("fs.statfs", Usage::ModuleMember("fs", "statfs"), "18.15.0"),
+  (
+    "fs.openAsBlob",
+    Usage::ModuleMember("fs", "openAsBlob"),
+    "19.8.0",
+  ),
+  ("fs.glob", Usage::ModuleMember("fs", "glob"), "22.0.0"),
+  (
+    "fs.promises.glob",
+    Usage::ModuleMember("fs/promises", "glob"),
+    "22.0.0",
+  ),
+  (
+    "util.parseArgs",
+    Usage::ModuleMember("util", "parseArgs"),
+    "18.3.0",
+  ),
+  (
+    "util.styleText",
+    Usage::ModuleMember("util", "styleText"),
+    "20.12.0",
+  ),
+  (
+    "util.aborted",
+    Usage::ModuleMember("util", "aborted"),
+    "19.7.0",
+  ),
+  (
+    "util.stripVTControlCharacters",
+    Usage::ModuleMember("util", "stripVTControlCharacters"),
+    "16.11.0",
+  ),
+  (
+    "events.once",
+    Usage::ModuleMember("events", "once"),
+    "11.13.0",
+  ),
+  ("events.on", Usage::ModuleMember("events", "on"), "13.6.0"),
+  (
+    "events.addAbortListener",
+    Usage::ModuleMember("events", "addAbortListener"),
+    "20.5.0",
+  ),
+  (
+    "crypto.randomUUID",
+    Usage::ModuleMember("crypto", "randomUUID"),
+    "14.17.0",
+  ),
+  (
+    "crypto.webcrypto",
+    Usage::ModuleMember("crypto", "webcrypto"),
+    "15.0.0",
+  ),
+  (
+    "crypto.hash",
+    Usage::ModuleMember("crypto", "hash"),
+    "21.7.0",
+  ),
+  (
+    "path.matchesGlob",
+    Usage::ModuleMember("path", "matchesGlob"),
+    "22.5.0",
+  ),
+  (
+    "module.createRequire",
+    Usage::ModuleMember("module", "createRequire"),
+    "12.2.0",
+  ),
+  (
+    "module.register",
+    Usage::ModuleMember("module", "register"),
+    "20.6.0",
+  ),
+  (
+    "process.getBuiltinModule",
+    Usage::Static("process", "getBuiltinModule"),
+    "22.3.0",
+  ),
+  (
+    "process.loadEnvFile",
+    Usage::Static("process", "loadEnvFile"),
+    "21.7.0",
+  ),
+  // globals
+  (
+    "AbortController",
+    Usage::Global("AbortController"),
+    "15.0.0",
+  ),
+  ("AbortSignal", Usage::Global("AbortSignal"), "15.0.0"),
+  (
+    "AbortSignal.timeout",
+    Usage::Static("AbortSignal", "timeout"),
+    "17.3.0",
+  ),
+  (
+    "AbortSignal.any",
+    Usage::Static("AbortSignal", "any"),
+    "20.3.0",
+  ),
+  ("AggregateError", Usage::Global("AggregateError"), "15.0.0"),
+  ("Blob", Usage::Global("Blob"), "18.0.0"),
+  (
+    "BroadcastChannel",
+    Usage::Global("BroadcastChannel"),
+    "18.0.0",
+  ),
+  ("CustomEvent", Usage::Global("CustomEvent"), "19.0.0"),
+  ("DOMException", Usage::Global("DOMException"), "17.0.0"),
+  ("Event", Usage::Global("Event"), "15.0.0"),
+  ("EventTarget", Usage::Global("EventTarget"), "15.0.0"),
+  ("File", Usage::Global("File"), "20.0.0"),
+  (
+    "FinalizationRegistry",
+    Usage::Global("FinalizationRegistry"),
+    "14.6.0",
+  ),
+  ("FormData", Usage::Global("FormData"), "18.0.0"),
+  ("Headers", Usage::Global("Headers"), "18.0.0"),
+  ("ReadableStream", Usage::Global("ReadableStream"), "18.0.0"),
+  ("Request", Usage::Global("Request"), "18.0.0"),
+  ("Response", Usage::Global("Response"), "18.0.0"),
+  ("TextDecoder", Usage::Global("TextDecoder"), "11.0.0"),
+  ("TextEncoder", Usage::Global("TextEncoder"), "11.0.0"),
+  ("URL", Usage::Global("URL"), "10.0.0"),
+  (
+    "URLSearchParams",
+    Usage::Global("URLSearchParams"),
+    "10.0.0",
+  ),
+  ("WeakRef", Usage::Global("WeakRef"), "14.6.0"),
+  ("WebSocket", Usage::Global("WebSocket"), "22.0.0"),
+  ("crypto", Usage::Global("crypto"), "19.0.0"),
+  ("fetch", Usage::Global("fetch"), "18.0.0"),
+  ("globalThis", Usage::Global("globalThis"), "12.0.0"),
+  ("navigator", Usage::Global("navigator"), "21.0.0"),
+  ("performance", Usage::Global("performance"), "16.0.0"),
+  ("queueMicrotask", Usage::Global("queueMicrotask"), "11.0.0"),
+  (
+    "structuredClone",
+    Usage::Global("structuredClone"),
+    "17.0.0",
+  ),
+  // ECMAScript built-ins
+  (
+    "Array.fromAsync",
+    Usage::Static("Array", "fromAsync"),
+    "22.0.0",
+  ),
+  ("Array.prototype.at", Usage::Instance("at"), "16.6.0"),
+  (
+    "Array.prototype.findLast",
+    Usage::Instance("findLast"),
+    "18.0.0",
+  ),
+  (
+    "Array.prototype.findLastIndex",
+    Usage::Instance("findLastIndex"),
+    "18.0.0",
+  ),
+  ("Array.prototype.flat", Usage::Instance("flat"), "11.0.0"),
+  (
+    "Array.prototype.flatMap",
+    Usage::Instance("flatMap"),
+    "11.0.0",
+  ),
+  (
+    "Array.prototype.toReversed",
+    Usage::Instance("toReversed"),
+    "20.0.0",
+  ),
+  (
+    "Array.prototype.toSorted",
+    Usage::Instance("toSorted"),
+    "20.0.0",
+  ),
+  (
+    "Array.prototype.toSpliced",
+    Usage::Instance("toSpliced"),
+    "20.0.0",
+  ),
+  ("Array.prototype.with", Usage::Instance("with"), "20.0.0"),
+  ("Map.groupBy", Usage::Static("Map", "groupBy"), "21.0.0"),
+  (
+    "Object.fromEntries",
+    Usage::Static("Object", "fromEntries"),
+    "12.0.0",
+  ),
+  (
+    "Object.groupBy",
+    Usage::Static("Object", "groupBy"),
+    "21.0.0",
+  ),
+  ("Object.hasOwn", Usage::Static("Object", "hasOwn"), "16.9.0"),
+  (
+    "Promise.allSettled",
+    Usage::Static("Promise", "allSettled"),
+    "12.9.0",
+  ),
+  ("Promise.any", Usage::Static("Promise", "any"), "15.0.0"),
+  (
+    "Promise.withResolvers",
+    Usage::Static("Promise", "withResolvers"),
+    "22.0.0",
+  ),
+  (
+    "Set.prototype.difference",
+    Usage::Instance("difference"),
+    "22.0.0",
+  ),
+  (
+    "Set.prototype.intersection",
+    Usage::Instance("intersection"),
+    "22.0.0",
+  ),
+  ("Set.prototype.union", Usage::Instance("union"), "22.0.0"),
+  (
+    "String.prototype.isWellFormed",
+    Usage::Instance("isWellFormed"),
+    "20.0.0",
+  ),
+  (
+    "String.prototype.matchAll",
+    Usage::Instance("matchAll"),
+    "12.0.0",
+  ),
+  (
+    "String.prototype.replaceAll",
+    Usage::Instance("replaceAll"),
+    "15.0.0",
+  ),
+  (
+    "String.prototype.toWellFormed",
+    Usage::Instance("toWellFormed"),
+    "20.0.0",
+  ),
+  (
+    "String.prototype.trimEnd",
+    Usage::Instance("trimEnd"),
+    "10.0.0",
+  ),
+  (
+    "String.prototype.trimStart",
+    Usage::Instance("trimStart"),
+    "10.0.0",
+  ),
+];
+
+// Scheme for core modules, e.g. import fs from "node:fs"
+const NODE_SCHEME: (&str, &str) = ("node:", "14.18.0");
+
+#[derive(Debug, Clone)]
+pub struct NodeApiMatch {
+  pub name: &'static str,
+  // first Node.js version shipping it
+  pub since: &'static str,
+  pub span: Span,
+  // the global it's read from is a local binding
+  pub shadowed: bool,
+}
+
+const ANY_VERSION: Version = Version {
+  major: 0,
+  minor: 0,
+  patch: 0,
+};
+
+// Lowest version matched by a semver range, e.g. ">=14.17 <20" | "^16 || ^18". None if the
+// range can't be parsed
+pub fn min_node_version(range: &str) -> Option<Version> {
+  range
+    .split("||")
+    .map(|set| {
+      // 14.17 - 18 => >=14.17
+      let set = set.split(" - ").next().unwrap_or_default();
+      set
+        .split_whitespace()
+        .filter(|comparator| !comparator.starts_with('<'))
+        .map(|comparator| {
+          let version = comparator.trim_start_matches(|c| matches!(c, '>' | '=' | '^' | '~' | 'v'));
+          parse_partial(version)
+        })
+        .try_fold(None::<Version>, |min, version| {
+          let version = version?;
+          Some(Some(min.map_or(version, |min| min.max(version))))
+        })
+        .map(|min| min.unwrap_or(ANY_VERSION))
+    })
+    .try_fold(None::<Version>, |min, version| {
+      let version = version?;
+      Some(Some(min.map_or(version, |min| min.min(version))))
+    })
+    .flatten()
+}
+
+// 14 | 14.x | 14.17.* | 14.17.0
+fn parse_partial(version: &str) -> Option<Version> {
+  if version.is_empty() || version == "*" {
+    return Some(ANY_VERSION);
+  }
+  let mut parts = version
+    .split('.')
+    .map(|part| match part {
+      "x" | "X" | "*" => Some(0),
+      _ => part.parse::<u32>().ok(),
+    })
+    .collect::<Option<Vec<_>>>()?
+    .into_iter();
+  Some(Version {
+    major: parts.next()?,
+    minor: parts.next().unwrap_or(0),
+    patch: parts.next().unwrap_or(0),
+  })
+}
+
+// Every Node.js API used in `program`, whether or not the version range supports it.
+// `program` went through the resolver with `unresolved_mark`
+pub fn analyze_node_apis(program: &Program, unresolved_mark: Mark) -> Vec<NodeApiMatch> {
+  let mut visitor = NodeApiVisitor {
+    matches: vec![],
+    modules: HashMap::new(),
+    unresolved: SyntaxContext::empty().apply_mark(unresolved_mark),
+  };
+  program.visit_with(&mut visitor);
+  visitor.matches
+}
+
+struct NodeApiVisitor {
+  matches: Vec<NodeApiMatch>,
+  // local binding => module, e.g. fsp => fs/promises
+  modules: HashMap<Id, String>,
+  unresolved: SyntaxContext,
+}
+
+impl NodeApiVisitor {
+  fn add(&mut self, span: Span, shadowed: bool, filter: impl Fn(&Usage) -> bool) {
+    for (name, usage, since) in NODE_APIS {
+      if filter(usage) {
+        self.matches.push(NodeApiMatch {
+          name,
+          since,
+          span,
+          shadowed,
+        });
+      }
+    }
+  }
+  // `src` of an import or require call
+  fn check_module(&mut self, span: Span, src: &str) {
+    if src.starts_with(NODE_SCHEME.0) {
+      self.matches.push(NodeApiMatch {
+        name: NODE_SCHEME.0,
+        since: NODE_SCHEME.1,
+        span,
+        shadowed: false,
+      });
+    }
+    let module = strip_scheme(src);
+    self.add(span, false, |usage| *usage == Usage::Module(module));
+  }
+  fn check_module_member(&mut self, span: Span, module: &str, member: &str) {
+    self.add(span, false, |usage| {
+      *usage == Usage::ModuleMember(module, member)
+    });
+  }
+  // fs | require("fs") | fs.promises
+  fn module_of(&self, expr: &Expr) -> Option<String> {
+    match expr.unwrap_parens() {
+      Expr::Ident(ident) => self.modules.get(&ident.to_id()).cloned(),
+      Expr::Call(call) => require_src(call).map(|src| strip_scheme(&src).to_string()),
+      Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+      }) if &*prop.sym == "promises" => self.module_of(obj).map(|m| format!("{}/promises", m)),
+      _ => None,
+    }
+  }
+  // const fs = require("fs") | const { promises: fsp, cp } = require("fs")
+  fn bind(&mut self, pat: &Pat, module: String) {
+    match pat {
+      Pat::Ident(BindingIdent { id, .. }) => {
+        self.modules.insert(id.to_id(), module);
+      }
+      Pat::Object(ObjectPat { props, .. }) => {
+        for prop in props {
+          let (key, span, value) = match prop {
+            ObjectPatProp::KeyValue(KeyValuePatProp {
+              key: PropName::Ident(key),
+              value,
+            }) => (&key.sym, key.span, Some(&**value)),
+            ObjectPatProp::Assign(AssignPatProp { key, .. }) => (&key.sym, key.span, None),
+            _ => continue,
+          };
+          if &**key == "promises" {
+            let module = format!("{}/promises", module);
+            match value {
+              Some(value) => self.bind(value, module),
+              None => {
+                self.modules.insert((key.clone(), span.ctxt), module);
+              }
+            }
+          } else {
+            self.check_module_member(span, &module, key);
+          }
+        }
+      }
+      _ => (),
+    }
+  }
+}
+
+impl Visit for NodeApiVisitor {
+  noop_visit_type!();
+
+  // import fs from "fs" | import { cp } from "node:fs/promises"
+  fn visit_import_decl(&mut self, n: &ImportDecl) {
+    self.check_module(n.src.span, &n.src.value);
+    let module = strip_scheme(&n.src.value).to_string();
+    for specifier in &n.specifiers {
+      match specifier {
+        ImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+        | ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+          self.modules.insert(local.to_id(), module.clone());
+        }
+        ImportSpecifier::Named(named) => {
+          let imported = match &named.imported {
+            Some(ModuleExportName::Ident(ident)) => &ident.sym,
+            Some(ModuleExportName::Str(s)) => &s.value,
+            None => &named.local.sym,
+          };
+          if &**imported == "promises" {
+            self
+              .modules
+              .insert(named.local.to_id(), format!("{}/promises", module));
+          } else {
+            self.check_module_member(named.span, &module, imported);
+          }
+        }
+      }
+    }
+  }
+
+  // export { cp } from "fs/promises"
+  fn visit_named_export(&mut self, n: &NamedExport) {
+    if let Some(src) = &n.src {
+      self.check_module(src.span, &src.value);
+    }
+  }
+
+  fn visit_export_all(&mut self, n: &ExportAll) {
+    self.check_module(n.src.span, &n.src.value);
+  }
+
+  // const fs = require("fs")
+  fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+    n.visit_children_with(self);
+    if let Some(module) = n.init.as_deref().and_then(|init| self.module_of(init)) {
+      self.bind(&n.name, module);
+    }
+  }
+
+  // require("node:test") | import("fs/promises")
+  fn visit_call_expr(&mut self, n: &CallExpr) {
+    n.visit_children_with(self);
+    let src = match &n.callee {
+      Callee::Import(..) => n.args.first().and_then(|arg| match &*arg.expr {
+        Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+        _ => None,
+      }),
+      _ => require_src(n),
+    };
+    if let Some(src) = src {
+      self.check_module(n.span, &src);
+    }
+  }
+
+  // structuredClone(a)
+  fn visit_expr(&mut self, n: &Expr) {
+    // import crypto from "crypto"
+    match n {
+      Expr::Ident(ident) if !self.modules.contains_key(&ident.to_id()) => {
+        let shadowed = ident.span.ctxt != self.unresolved;
+        self.add(ident.span, shadowed, |usage| {
+          *usage == Usage::Global(&ident.sym)
+        });
+      }
+      _ => (),
+    }
+    n.visit_children_with(self);
+  }
+
+  // fs.promises.cp | AbortSignal.timeout | a.findLast
+  fn visit_member_expr(&mut self, n: &MemberExpr) {
+    n.visit_children_with(self);
+    let prop = match &n.prop {
+      MemberProp::Ident(ident) => &ident.sym,
+      MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+        Expr::Lit(Lit::Str(s)) => &s.value,
+        _ => return,
+      },
+      MemberProp::PrivateName(..) => return,
+    };
+    if let Some(module) = self.module_of(&n.obj) {
+      self.check_module_member(n.span, &module, prop);
+      return;
+    }
+    if let Expr::Ident(obj) = &*n.obj {
+      if NODE_APIS
+        .iter()
+        .any(|(_, usage, _)| matches!(usage, Usage::Static(o, _) if *o == &*obj.sym))
+      {
+        let shadowed = obj.span.ctxt != self.unresolved;
+        self.add(n.span, shadowed, |usage| {
+          *usage == Usage::Static(&obj.sym, prop)
+        });
+        return;
+      }
+    }
+    self.add(n.span, false, |usage| *usage == Usage::Instance(prop));
+  }
+}
+
+fn strip_scheme(src: &str) -> &str {
+  src.strip_prefix(NODE_SCHEME.0).unwrap_or(src)
+}
+
+// require("fs")
+fn require_src(call: &CallExpr) -> Option<String> {
+  match (&call.callee, call.args.first()) {
+    (Callee::Expr(callee), Some(arg)) => match (&**callee, &*arg.expr) {
+      (Expr::Ident(callee), Expr::Lit(Lit::Str(src))) if &*callee.sym == "require" => {
+        Some(src.value.to_string())
+      }
+      _ => None,
+    },
+    _ => None,
+  }
+}
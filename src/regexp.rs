@@ -0,0 +1,132 @@
+// Syntax inside regex literals, e.g. /(?<year>\d{4})/u
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegexFeature {
+  // /a/y
+  StickyFlag,
+  // /a/s
+  DotAllFlag,
+  // /a/u
+  UnicodeFlag,
+  // /a/v
+  UnicodeSetsFlag,
+  // /a/d
+  HasIndicesFlag,
+  // /(?<name>a)/
+  NamedCapturingGroups,
+  // /(?<=a)b/ /(?<!a)b/
+  Lookbehind,
+  // /\p{Letter}/u
+  UnicodePropertyEscapes,
+  // /(?i:a)/
+  Modifiers,
+  // /(?<y>\d{4})-\d{2}|\d{2}-(?<y>\d{4})/, reported on the repeated group
+  DuplicateNamedGroups,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RegexMatch {
+  pub feature: RegexFeature,
+  // offsets relative to the leading `/` of `/exp/flags`
+  pub lo: u32,
+  pub hi: u32,
+}
+
+// Scan `exp` and `flags` of a regex and report every feature with its byte range in the
+// literal source `/exp/flags`
+pub fn analyze_regex(exp: &str, flags: &str) -> Vec<RegexMatch> {
+  let mut matches = vec![];
+  let mut push = |feature: RegexFeature, lo: usize, hi: usize| {
+    matches.push(RegexMatch {
+      feature,
+      lo: lo as u32,
+      hi: hi as u32,
+    })
+  };
+
+  let flags_offset = exp.len() + 2;
+  for (i, flag) in flags.char_indices() {
+    let feature = match flag {
+      'y' => RegexFeature::StickyFlag,
+      's' => RegexFeature::DotAllFlag,
+      'u' => RegexFeature::UnicodeFlag,
+      'v' => RegexFeature::UnicodeSetsFlag,
+      'd' => RegexFeature::HasIndicesFlag,
+      _ => continue,
+    };
+    push(feature, flags_offset + i, flags_offset + i + 1);
+  }
+
+  let unicode_sets = flags.contains('v');
+  let unicode = flags.contains('u') || unicode_sets;
+  let bytes = exp.as_bytes();
+  // pattern starts after the leading `/`
+  let offset = 1;
+  let mut class_depth = 0;
+  let mut group_names = vec![];
+  let mut i = 0;
+  while i < bytes.len() {
+    match bytes[i] {
+      b'\\' => {
+        // \p{...} and \P{...} are identity escapes without `u` or `v`
+        if unicode
+          && matches!(bytes.get(i + 1), Some(b'p' | b'P'))
+          && bytes.get(i + 2) == Some(&b'{')
+        {
+          if let Some(end) = exp[i..].find('}') {
+            push(
+              RegexFeature::UnicodePropertyEscapes,
+              offset + i,
+              offset + i + end + 1,
+            );
+            i += end + 1;
+            continue;
+          }
+        }
+        i += 2;
+        continue;
+      }
+      // `v` mode allows nested classes, e.g. /[\p{L}--[a-z]]/v
+      b'[' if class_depth == 0 || unicode_sets => class_depth += 1,
+      b']' if class_depth > 0 => class_depth -= 1,
+      b'(' if class_depth == 0 => {
+        let rest = &bytes[i + 1..];
+        if rest.starts_with(b"?<=") || rest.starts_with(b"?<!") {
+          push(RegexFeature::Lookbehind, offset + i, offset + i + 4);
+        } else if rest.starts_with(b"?<") {
+          if let Some(end) = exp[i..].find('>') {
+            let name = &exp[i + 3..i + end];
+            let feature = if group_names.contains(&name) {
+              RegexFeature::DuplicateNamedGroups
+            } else {
+              group_names.push(name);
+              RegexFeature::NamedCapturingGroups
+            };
+            push(feature, offset + i, offset + i + end + 1);
+          }
+        } else if let Some(len) = modifiers_len(rest) {
+          push(RegexFeature::Modifiers, offset + i, offset + i + len + 1);
+        }
+      }
+      _ => (),
+    }
+    i += 1;
+  }
+  matches
+}
+
+// Length of `?ims-ims:` at the start of `rest`
+fn modifiers_len(rest: &[u8]) -> Option<usize> {
+  if rest.first() != Some(&b'?') {
+    return None;
+  }
+  let len = rest[1..]
+    .iter()
+    .take_while(|b| matches!(b, b'i' | b'm' | b's' | b'-'))
+    .count();
+  let modifiers = &rest[1..1 + len];
+  if modifiers.iter().any(|b| *b != b'-') && rest.get(1 + len) == Some(&b':') {
+    Some(len + 2)
+  } else {
+    None
+  }
+}
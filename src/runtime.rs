@@ -0,0 +1,135 @@
+use swc_core::ecma::ast::*;
+
+// Helpers injected by bundlers around the authored code
+const RUNTIME_NAMES: &[&str] = &[
+  // webpack
+  "__webpack_require__",
+  "__webpack_module_cache__",
+  // rollup
+  "commonjsGlobal",
+  "getDefaultExportFromCjs",
+  "getAugmentedNamespace",
+  "_interopDefaultLegacy",
+  "_interopNamespace",
+  "_interopNamespaceDefault",
+  "_mergeNamespaces",
+  // esbuild
+  "__create",
+  "__defProp",
+  "__defProps",
+  "__getOwnPropDesc",
+  "__getOwnPropDescs",
+  "__getOwnPropNames",
+  "__getOwnPropSymbols",
+  "__getProtoOf",
+  "__hasOwnProp",
+  "__propIsEnum",
+  "__defNormalProp",
+  "__spreadValues",
+  "__spreadProps",
+  "__objRest",
+  "__name",
+  "__require",
+  "__esm",
+  "__commonJS",
+  "__export",
+  "__copyProps",
+  "__reExport",
+  "__toESM",
+  "__toCommonJS",
+  "__publicField",
+  "__accessCheck",
+  "__privateGet",
+  "__privateAdd",
+  "__privateSet",
+  "__privateMethod",
+  "__async",
+  // vite
+  "__vitePreload",
+  "__vite__mapDeps",
+  "__vite_legacy_guard",
+];
+
+fn is_runtime_name(name: &str) -> bool {
+  RUNTIME_NAMES.contains(&name)
+}
+
+// function __webpack_require__(moduleId) {} | var __defProp = Object.defineProperty
+pub fn is_runtime_stmt(stmt: &Stmt) -> bool {
+  match stmt {
+    Stmt::Decl(Decl::Fn(FnDecl { ident, .. })) => is_runtime_name(&ident.sym),
+    Stmt::Decl(Decl::Var(var)) => {
+      !var.decls.is_empty()
+        && var.decls.iter().all(|decl| match &decl.name {
+          Pat::Ident(BindingIdent { id, .. }) => is_runtime_name(&id.sym),
+          _ => false,
+        })
+    }
+    Stmt::Expr(ExprStmt { expr, .. }) => is_webpack_runtime_assign(expr) || is_runtime_iife(expr),
+    _ => false,
+  }
+}
+
+// __webpack_require__.d = (exports, definition) => {}
+fn is_webpack_runtime_assign(expr: &Expr) -> bool {
+  match expr.unwrap_parens() {
+    Expr::Assign(AssignExpr {
+      left: PatOrExpr::Expr(left),
+      ..
+    }) => is_webpack_require_member(left),
+    Expr::Assign(AssignExpr {
+      left: PatOrExpr::Pat(left),
+      ..
+    }) => match &**left {
+      Pat::Expr(left) => is_webpack_require_member(left),
+      _ => false,
+    },
+    _ => false,
+  }
+}
+
+fn is_webpack_require_member(expr: &Expr) -> bool {
+  match expr {
+    Expr::Member(MemberExpr { obj, .. }) => {
+      matches!(&**obj, Expr::Ident(Ident { sym, .. }) if &**sym == "__webpack_require__")
+    }
+    _ => false,
+  }
+}
+
+// (() => { __webpack_require__.d = ... })() | (function polyfill() { ... })()
+// Only the helper wrappers webpack 5 emits, never the bootstrap calling `__webpack_require__`
+// with the modules, its statements are tagged one by one instead
+fn is_runtime_iife(expr: &Expr) -> bool {
+  let callee = match expr.unwrap_parens() {
+    Expr::Call(CallExpr {
+      callee: Callee::Expr(callee),
+      args,
+      ..
+    }) if args.is_empty() => callee.unwrap_parens(),
+    _ => return false,
+  };
+  let stmts = match callee {
+    // vite modulepreload polyfill
+    Expr::Fn(FnExpr {
+      ident: Some(ident), ..
+    }) if &*ident.sym == "polyfill" => return true,
+    Expr::Fn(FnExpr { function, .. }) => function.body.as_ref().map(|body| &body.stmts),
+    Expr::Arrow(ArrowExpr { body, .. }) => match &**body {
+      BlockStmtOrExpr::BlockStmt(body) => Some(&body.stmts),
+      BlockStmtOrExpr::Expr(..) => None,
+    },
+    _ => None,
+  };
+  stmts.map_or(false, |stmts| {
+    let assigns_runtime = stmts.iter().any(|stmt| match stmt {
+      Stmt::Expr(ExprStmt { expr, .. }) => is_webpack_runtime_assign(expr),
+      _ => false,
+    });
+    let bootstrap = stmts.iter().any(|stmt| match stmt {
+      Stmt::Decl(Decl::Fn(FnDecl { ident, .. })) => &*ident.sym == "__webpack_require__",
+      _ => false,
+    });
+    assigns_runtime && !bootstrap
+  })
+}
@@ -0,0 +1,111 @@
+use swc_core::common::{BytePos, SourceFile, Span};
+use swc_core::ecma::ast::{AssignOp, EsVersion, Program, Regex};
+use swc_core::ecma::visit::{noop_visit_type, Visit, VisitWith};
+use swc_ecma_parser::lexer::Lexer;
+use swc_ecma_parser::token::{BinOpToken, Token};
+use swc_ecma_parser::{StringInput, Syntax};
+
+// Syntax dropped by the parser, only visible in the token stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFeature {
+  // function a(x,) {} | a(x,)
+  TrailingFunctionComma,
+  // { a: 1, } | [1, 2,]
+  TrailingLiteralComma,
+  // #!/usr/bin/env node
+  Hashbang,
+  // U+2028 or U+2029 written raw inside a string literal
+  LineSeparator,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TokenMatch {
+  pub feature: TokenFeature,
+  pub span: Span,
+}
+
+// Lex `source_file` again and report every feature with the span of its token. `program`
+// is the parsed `source_file`, the lexer alone can't tell a regex from a division
+pub fn analyze_tokens(
+  source_file: &SourceFile,
+  syntax: Syntax,
+  program: &Program,
+) -> Vec<TokenMatch> {
+  let mut regex_starts = RegexVisitor::default();
+  program.visit_with(&mut regex_starts);
+  let regex_starts = regex_starts.starts;
+
+  let mut matches = vec![];
+  let mut lexer = Lexer::new(
+    syntax,
+    EsVersion::EsNext,
+    StringInput::new(&source_file.src, source_file.start_pos, source_file.end_pos),
+    None,
+  );
+  let mut prev_comma: Option<Span> = None;
+  while let Some(token) = lexer.next() {
+    match &token.token {
+      // re-lex `/a/` the way the parser does
+      Token::BinOp(BinOpToken::Div) | Token::AssignOp(AssignOp::DivAssign)
+        if regex_starts.contains(&token.span.lo) =>
+      {
+        lexer.set_next_regexp(Some(token.span.lo));
+        lexer.next();
+        lexer.set_next_regexp(None);
+      }
+      // `,` directly before `)` only appears in parameter lists and call arguments
+      Token::RParen => {
+        if let Some(span) = prev_comma {
+          matches.push(TokenMatch {
+            feature: TokenFeature::TrailingFunctionComma,
+            span,
+          });
+        }
+      }
+      // also closes destructuring patterns and import lists, which need es2015 anyway
+      Token::RBrace | Token::RBracket => {
+        if let Some(span) = prev_comma {
+          matches.push(TokenMatch {
+            feature: TokenFeature::TrailingLiteralComma,
+            span,
+          });
+        }
+      }
+      Token::Shebang(..) => matches.push(TokenMatch {
+        feature: TokenFeature::Hashbang,
+        span: token.span,
+      }),
+      // always valid in templates, only strings were affected
+      Token::Str { raw, .. } => {
+        for (i, c) in raw.char_indices() {
+          if c == '\u{2028}' || c == '\u{2029}' {
+            let lo = token.span.lo + BytePos(i as u32);
+            matches.push(TokenMatch {
+              feature: TokenFeature::LineSeparator,
+              span: Span::new(lo, lo + BytePos(c.len_utf8() as u32), token.span.ctxt),
+            });
+          }
+        }
+      }
+      _ => (),
+    }
+    prev_comma = match token.token {
+      Token::Comma => Some(token.span),
+      _ => None,
+    };
+  }
+  matches
+}
+
+#[derive(Default)]
+struct RegexVisitor {
+  starts: Vec<BytePos>,
+}
+
+impl Visit for RegexVisitor {
+  noop_visit_type!();
+
+  fn visit_regex(&mut self, n: &Regex) {
+    self.starts.push(n.span.lo);
+  }
+}
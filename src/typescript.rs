@@ -0,0 +1,154 @@
+use swc_core::common::{Span, Spanned};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+
+// Remove type-only TypeScript constructs from `program`, interfaces, type aliases, `declare`s,
+// overloads, abstract members, `this` parameters and type-only imports/exports. Enums, namespaces
+// and parameter properties are kept, they are emitted as code
+// Returns the spans of the removed code, of every type, e.g. `: string` or `<T,>`, and of enums,
+// `enum E { A, }` has no literal trailing comma
+pub fn strip_types(program: &mut Program) -> Vec<Span> {
+  let mut visitor = StripTypes::default();
+  program.visit_mut_with(&mut visitor);
+  visitor.spans
+}
+
+#[derive(Default)]
+struct StripTypes {
+  spans: Vec<Span>,
+}
+
+impl StripTypes {
+  fn retain<T: Spanned>(&mut self, items: &mut Vec<T>, keep: impl Fn(&T) -> bool) {
+    items.retain(|item| {
+      let keep = keep(item);
+      if !keep {
+        self.spans.push(item.span());
+      }
+      keep
+    });
+  }
+}
+
+impl VisitMut for StripTypes {
+  fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
+    self.retain(n, |item| match item {
+      ModuleItem::Stmt(stmt) => is_concrete_stmt(stmt),
+      ModuleItem::ModuleDecl(decl) => is_concrete_module_decl(decl),
+    });
+    n.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_stmts(&mut self, n: &mut Vec<Stmt>) {
+    self.retain(n, is_concrete_stmt);
+    n.visit_mut_children_with(self);
+  }
+
+  // import { type A, b } from 'a'
+  fn visit_mut_import_specifiers(&mut self, n: &mut Vec<ImportSpecifier>) {
+    self.retain(
+      n,
+      |specifier| !matches!(specifier, ImportSpecifier::Named(named) if named.is_type_only),
+    );
+  }
+
+  // export { type A, b }
+  fn visit_mut_export_specifiers(&mut self, n: &mut Vec<ExportSpecifier>) {
+    self.retain(
+      n,
+      |specifier| !matches!(specifier, ExportSpecifier::Named(named) if named.is_type_only),
+    );
+  }
+
+  // class A implements B { [key: string]: any; declare a: string; abstract b(): void }
+  fn visit_mut_class(&mut self, n: &mut Class) {
+    self
+      .spans
+      .extend(n.implements.drain(..).map(|implement| implement.span));
+    self.retain(&mut n.body, |member| match member {
+      ClassMember::TsIndexSignature(..) => false,
+      ClassMember::Constructor(Constructor { body: None, .. }) => false,
+      ClassMember::Method(ClassMethod {
+        is_abstract,
+        function,
+        ..
+      })
+      | ClassMember::PrivateMethod(PrivateMethod {
+        is_abstract,
+        function,
+        ..
+      }) => !is_abstract && function.body.is_some(),
+      ClassMember::ClassProp(ClassProp {
+        declare,
+        is_abstract,
+        ..
+      }) => !declare && !is_abstract,
+      _ => true,
+    });
+    n.visit_mut_children_with(self);
+  }
+
+  // function f(this: Window, a) {}
+  fn visit_mut_params(&mut self, n: &mut Vec<Param>) {
+    if n.first().is_some_and(
+      |param| matches!(&param.pat, Pat::Ident(BindingIdent { id, .. }) if &*id.sym == "this"),
+    ) {
+      self.spans.push(n.remove(0).span);
+    }
+    n.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_ts_type(&mut self, n: &mut TsType) {
+    self.spans.push(n.span());
+  }
+
+  fn visit_mut_ts_type_param_decl(&mut self, n: &mut TsTypeParamDecl) {
+    self.spans.push(n.span);
+  }
+
+  fn visit_mut_ts_type_param_instantiation(&mut self, n: &mut TsTypeParamInstantiation) {
+    self.spans.push(n.span);
+  }
+
+  fn visit_mut_ts_enum_decl(&mut self, n: &mut TsEnumDecl) {
+    self.spans.push(n.span);
+    n.visit_mut_children_with(self);
+  }
+}
+
+fn is_concrete_stmt(stmt: &Stmt) -> bool {
+  match stmt {
+    Stmt::Decl(decl) => is_concrete_decl(decl),
+    _ => true,
+  }
+}
+
+fn is_concrete_module_decl(decl: &ModuleDecl) -> bool {
+  match decl {
+    ModuleDecl::Import(import) => !import.type_only,
+    ModuleDecl::ExportDecl(export) => is_concrete_decl(&export.decl),
+    ModuleDecl::ExportNamed(export) => !export.type_only,
+    ModuleDecl::ExportAll(export) => !export.type_only,
+    ModuleDecl::ExportDefaultDecl(export) => match &export.decl {
+      DefaultDecl::Fn(function) => function.function.body.is_some(),
+      DefaultDecl::TsInterfaceDecl(..) => false,
+      DefaultDecl::Class(..) => true,
+    },
+    ModuleDecl::TsImportEquals(import) => !import.is_type_only,
+    ModuleDecl::TsNamespaceExport(..) => false,
+    ModuleDecl::ExportDefaultExpr(..) | ModuleDecl::TsExportAssignment(..) => true,
+  }
+}
+
+// `declare` declarations and overloads have no code
+fn is_concrete_decl(decl: &Decl) -> bool {
+  match decl {
+    Decl::TsInterface(..) | Decl::TsTypeAlias(..) => false,
+    Decl::Fn(function) => !function.declare && function.function.body.is_some(),
+    Decl::Class(class) => !class.declare,
+    Decl::Var(var) => !var.declare,
+    Decl::TsEnum(ts_enum) => !ts_enum.declare,
+    Decl::TsModule(module) => !module.declare && !module.global && module.body.is_some(),
+    Decl::Using(..) => true,
+  }
+}
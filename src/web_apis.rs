@@ -0,0 +1,194 @@
+use std::sync::OnceLock;
+
+use preset_env_base::version::Version;
+use preset_env_base::{BrowserData, Versions};
+use serde_json::Value;
+use swc_core::common::{Mark, Span, SyntaxContext};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{noop_visit_type, Visit, VisitWith};
+
+// Hand-picked list of about 20 APIs, `api.*` entries with their `__compat.support` copied from
+// https://github.com/mdn/browser-compat-data. Not generated, extend it by hand
+const SNAPSHOT: &str = include_str!("../data/web-apis.json");
+
+// Interfaces exposed through a global instance, e.g. navigator.clipboard
+pub const GLOBAL_INSTANCES: &[(&str, &str)] = &[
+  ("Navigator", "navigator"),
+  ("Document", "document"),
+  ("Crypto", "crypto"),
+];
+
+// Objects a global can be read from, e.g. window.ResizeObserver
+const GLOBAL_OBJECTS: &[&str] = &["global", "globalThis", "self", "window"];
+
+// How a web API shows up in code
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Usage {
+  // new ResizeObserver(cb)
+  Global(String),
+  // AbortSignal.timeout(100) | navigator.clipboard
+  Static(String, String),
+  // el.replaceChildren()
+  Instance(String),
+}
+
+#[derive(Debug, Clone)]
+struct WebApi {
+  // path in browser-compat-data without the `api.` prefix, e.g. Element.replaceChildren
+  name: String,
+  usage: Usage,
+  support: Versions,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebApiMatch {
+  pub name: String,
+  pub support: Versions,
+  pub span: Span,
+  // the global it's read from is a local binding
+  pub shadowed: bool,
+  // an instance member matched by its name alone, e.g. `a.closest()` with `a` maybe not an Element
+  pub low_confidence: bool,
+}
+
+fn web_apis() -> &'static [WebApi] {
+  static WEB_APIS: OnceLock<Vec<WebApi>> = OnceLock::new();
+  WEB_APIS.get_or_init(|| {
+    let snapshot: Value = serde_json::from_str(SNAPSHOT).expect("invalid web-apis.json");
+    let mut apis = vec![];
+    let interfaces = snapshot["api"]
+      .as_object()
+      .expect("missing `api` in web-apis.json");
+    for (interface, data) in interfaces {
+      if let Some(support) = data["__compat"]["support"].as_object() {
+        apis.push(WebApi {
+          name: interface.clone(),
+          usage: Usage::Global(interface.clone()),
+          support: to_versions(support),
+        });
+      }
+      let members = data.as_object().into_iter().flatten();
+      for (member, data) in members.filter(|(member, _)| *member != "__compat") {
+        let Some(support) = data["__compat"]["support"].as_object() else {
+          continue;
+        };
+        let instance = GLOBAL_INSTANCES
+          .iter()
+          .find(|(name, _)| name == interface)
+          .map(|(_, instance)| instance);
+        let usage = match (member.strip_suffix("_static"), instance) {
+          (Some(member), _) => Usage::Static(interface.clone(), member.to_string()),
+          (None, Some(instance)) => Usage::Static(instance.to_string(), member.clone()),
+          (None, None) => Usage::Instance(member.clone()),
+        };
+        apis.push(WebApi {
+          name: format!("{}.{}", interface, member.trim_end_matches("_static")),
+          usage,
+          support: to_versions(support),
+        });
+      }
+    }
+    apis
+  })
+}
+
+// `version_added` of each browser, `false` and `null` mean not supported
+fn to_versions(support: &serde_json::Map<String, Value>) -> Versions {
+  let version = |browser: &str| -> Option<Version> {
+    match &support.get(browser)?["version_added"] {
+      Value::String(v) => v.trim_start_matches('≤').parse().ok(),
+      Value::Bool(true) => "0".parse().ok(),
+      _ => None,
+    }
+  };
+  BrowserData {
+    chrome: version("chrome"),
+    and_chr: version("chrome_android"),
+    edge: version("edge"),
+    firefox: version("firefox"),
+    and_ff: version("firefox_android"),
+    ie: version("ie"),
+    opera: version("opera"),
+    op_mob: version("opera_android"),
+    safari: version("safari"),
+    ios: version("safari_ios"),
+    samsung: version("samsunginternet_android"),
+    android: version("webview_android"),
+    node: version("nodejs"),
+    ..Default::default()
+  }
+}
+
+// Every web API used in `program`, whether or not the targets support it
+// `program` went through the resolver with `unresolved_mark`
+pub fn analyze_web_apis(program: &Program, unresolved_mark: Mark) -> Vec<WebApiMatch> {
+  let mut visitor = WebApiVisitor {
+    matches: vec![],
+    unresolved: SyntaxContext::empty().apply_mark(unresolved_mark),
+  };
+  program.visit_with(&mut visitor);
+  visitor.matches
+}
+
+struct WebApiVisitor {
+  matches: Vec<WebApiMatch>,
+  unresolved: SyntaxContext,
+}
+
+impl WebApiVisitor {
+  // `global` is the identifier the API is read from, None for instance members
+  fn add(&mut self, span: Span, global: Option<&Ident>, usage: Usage) {
+    let shadowed = global.is_some_and(|global| global.span.ctxt != self.unresolved);
+    for api in web_apis().iter().filter(|api| api.usage == usage) {
+      self.matches.push(WebApiMatch {
+        name: api.name.clone(),
+        support: api.support,
+        span,
+        shadowed,
+        low_confidence: global.is_none(),
+      });
+    }
+  }
+}
+
+impl Visit for WebApiVisitor {
+  noop_visit_type!();
+
+  // new ResizeObserver(cb)
+  fn visit_expr(&mut self, n: &Expr) {
+    if let Expr::Ident(ident) = n {
+      self.add(
+        ident.span,
+        Some(ident),
+        Usage::Global(ident.sym.to_string()),
+      );
+    }
+    n.visit_children_with(self);
+  }
+
+  // navigator.clipboard | window.ResizeObserver | el.replaceChildren()
+  fn visit_member_expr(&mut self, n: &MemberExpr) {
+    n.visit_children_with(self);
+    let prop = match &n.prop {
+      MemberProp::Ident(ident) => ident.sym.to_string(),
+      MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+        Expr::Lit(Lit::Str(s)) => s.value.to_string(),
+        _ => return,
+      },
+      MemberProp::PrivateName(..) => return,
+    };
+    match &*n.obj {
+      Expr::Ident(obj) if GLOBAL_OBJECTS.contains(&&*obj.sym) => {
+        self.add(n.span, Some(obj), Usage::Global(prop));
+      }
+      Expr::Ident(obj)
+        if web_apis()
+          .iter()
+          .any(|api| matches!(&api.usage, Usage::Static(o, _) if *o == *obj.sym)) =>
+      {
+        self.add(n.span, Some(obj), Usage::Static(obj.sym.to_string(), prop));
+      }
+      _ => self.add(n.span, None, Usage::Instance(prop)),
+    }
+  }
+}
@@ -27,6 +27,24 @@ const glob = async (cwd: string, feature: string, shouldFound = true) => {
     expect(result.features[feature]).toBe(shouldFound)
   }
 }
+const globDetail = async (cwd: string, feature: string) => {
+  const files = fg.sync('**.js', {
+    onlyFiles: true,
+    cwd,
+    absolute: true,
+  })
+  for (const filename of files) {
+    const code = (await fs.readFile(filename)).toString('utf-8')
+    const result = detect({
+      filename,
+      code,
+      browserslist: 'IE 11',
+    })
+    const detail = result.details.find(detail => detail.feature === feature)
+    expect(detail).toBeDefined()
+    expect(detail!.e).toBeGreaterThan(detail!.s)
+  }
+}
 // eslint-disable-next-line unused-imports/no-unused-vars
 const single = async (filename: string, feature: string, shouldFound = true) => {
   const code = (await fs.readFile(filename)).toString('utf-8')
@@ -39,6 +57,112 @@ const single = async (filename: string, feature: string, shouldFound = true) =>
 }
 const spread = path.join(fixtures, './spread/should')
 
+describe('esnext', () => {
+  describe('autoAccessors', () => {
+    const yes = path.join(fixtures, './AutoAccessors/should')
+    const no = path.join(fixtures, './AutoAccessors/should-not')
+    it('should', async () => {
+      await glob(yes, 'autoAccessors')
+    })
+    it('should-not', async () => {
+      await glob(no, 'autoAccessors', false)
+    })
+  })
+  describe('decorators', () => {
+    const yes = path.join(fixtures, './Decorators/should')
+    it('should', async () => {
+      await glob(yes, 'decorators')
+    })
+  })
+  describe('explicitResourceManagement', () => {
+    const yes = path.join(fixtures, './ExplicitResourceManagement/should')
+    const no = path.join(fixtures, './ExplicitResourceManagement/should-not')
+    it('should', async () => {
+      await glob(yes, 'explicitResourceManagement')
+    })
+    it('should-not', async () => {
+      await glob(no, 'explicitResourceManagement', false)
+    })
+    it('detail', async () => {
+      await globDetail(yes, 'explicit_resource_management')
+    })
+  })
+})
+
+describe('es2025', () => {
+  describe('regexModifiers', () => {
+    const yes = path.join(fixtures, './RegexModifiers/should')
+    const no = path.join(fixtures, './RegexModifiers/should-not')
+    it('should', async () => {
+      await glob(yes, 'regexModifiers')
+    })
+    it('should-not', async () => {
+      await glob(no, 'regexModifiers', false)
+    })
+  })
+  describe('importAttributes', () => {
+    const yes = path.join(fixtures, './ImportAttributes/should')
+    it('should', async () => {
+      await glob(yes, 'importAttributes')
+    })
+  })
+  describe('duplicateNamedGroupsRegex', () => {
+    const yes = path.join(fixtures, './DuplicateNamedGroupsRegex/should')
+    const no = path.join(fixtures, './DuplicateNamedGroupsRegex/should-not')
+    it('should', async () => {
+      await glob(yes, 'duplicateNamedGroupsRegex')
+    })
+    it('should-not', async () => {
+      await glob(no, 'duplicateNamedGroupsRegex', false)
+    })
+  })
+  it('target', () => {
+    const result = detect({
+      filename: 'input.js',
+      code: 'const a = /(?i:a)/',
+      browserslist: 'chrome 120',
+      target: 'es2024',
+    })
+    expect(result.features.regexModifiers).toBe(true)
+    expect(result.esVersions.Es2025).toBe(true)
+    expect(() => detect({
+      filename: 'input.js',
+      code: 'const a = 1',
+      browserslist: 'chrome 120',
+      target: 'es2030',
+    })).toThrow('unknown target: es2030')
+  })
+})
+
+describe('es2024', () => {
+  describe('unicodeSetsRegex', () => {
+    const yes = path.join(fixtures, './UnicodeSetsRegex/should')
+    const no = path.join(fixtures, './UnicodeSetsRegex/should-not')
+    it('should', async () => {
+      await glob(yes, 'unicodeSetsRegex')
+    })
+    it('should-not', async () => {
+      await glob(no, 'unicodeSetsRegex', false)
+    })
+  })
+})
+
+describe('es2023', () => {
+  describe('hashbang', () => {
+    const yes = path.join(fixtures, './Hashbang/should')
+    const no = path.join(fixtures, './Hashbang/should-not')
+    it('should', async () => {
+      await glob(yes, 'hashbang')
+    })
+    it('should-not', async () => {
+      await glob(no, 'hashbang', false)
+    })
+    it('detail', async () => {
+      await globDetail(yes, 'hashbang')
+    })
+  })
+})
+
 describe('es2022', () => {
   const classStaticBlock = path.join(fixtures, './ClassStaticBlock')
   const privateMethods = path.join(fixtures, './PrivateMethods')
@@ -73,6 +197,48 @@ describe('es2022', () => {
     })
     expect(result.features.classProperties).toBe(true)
   })
+  describe('privateFields', () => {
+    const yes = path.join(fixtures, './PrivateFields/should')
+    const no = path.join(fixtures, './PrivateFields/should-not')
+    it('should', async () => {
+      await glob(yes, 'privateFields')
+    })
+    it('should-not', async () => {
+      await glob(no, 'privateFields', false)
+    })
+  })
+  describe('staticPrivate', () => {
+    const yes = path.join(fixtures, './StaticPrivate/should')
+    const no = path.join(fixtures, './StaticPrivate/should-not')
+    it('should', async () => {
+      await glob(yes, 'staticPrivate')
+    })
+    it('should-not', async () => {
+      await glob(no, 'staticPrivate', false)
+    })
+  })
+  describe('privatePropertyInObject', () => {
+    const yes = path.join(fixtures, './PrivatePropertyInObject/should')
+    it('should', async () => {
+      await glob(yes, 'privatePropertyInObject')
+    })
+  })
+  describe('topLevelAwait', () => {
+    const yes = path.join(fixtures, './TopLevelAwait/should')
+    const no = path.join(fixtures, './TopLevelAwait/should-not')
+    it('should', async () => {
+      await glob(yes, 'topLevelAwait')
+    })
+    it('should-not', async () => {
+      await glob(no, 'topLevelAwait', false)
+    })
+  })
+  describe('hasIndicesRegex', () => {
+    const yes = path.join(fixtures, './HasIndicesRegex/should')
+    it('should', async () => {
+      await glob(yes, 'hasIndicesRegex')
+    })
+  })
 })
 
 describe('es2021', () => {
@@ -80,6 +246,15 @@ describe('es2021', () => {
   it('logicalAssignmentOperators', async () => {
     await glob(yes, 'logicalAssignmentOperators')
   })
+  it('logicalAssignmentOperators details', async () => {
+    await globDetail(yes, 'logical_assignment_operators')
+  })
+  describe('numericSeparator', () => {
+    const yes = path.join(fixtures, './NumericSeparator/should')
+    it('should', async () => {
+      await glob(yes, 'numericSeparator')
+    })
+  })
 })
 
 describe('es2020', () => {
@@ -106,6 +281,46 @@ describe('es2020', () => {
     })
     expect(result.features.optionalChaining).toBe(true)
   })
+
+  describe('dynamicImport', () => {
+    const yes = path.join(fixtures, './DynamicImport/should')
+    it('should', async () => {
+      await glob(yes, 'dynamicImport')
+    })
+  })
+  describe('importMeta', () => {
+    const yes = path.join(fixtures, './ImportMeta/should')
+    it('should', async () => {
+      await glob(yes, 'importMeta')
+    })
+  })
+  describe('exportNamespaceFrom', () => {
+    const yes = path.join(fixtures, './ExportNamespaceFrom/should')
+    const no = path.join(fixtures, './ExportNamespaceFrom/should-not')
+    it('should', async () => {
+      await glob(yes, 'exportNamespaceFrom')
+    })
+    it('should-not', async () => {
+      await glob(no, 'exportNamespaceFrom', false)
+    })
+  })
+  describe('bigInt', () => {
+    const yes = path.join(fixtures, './BigInt/should')
+    it('should', async () => {
+      await glob(yes, 'bigInt')
+    })
+    it('not transformable', async () => {
+      const filename = path.join(yes, './1.js')
+      const code = (await fs.readFile(filename)).toString('utf-8')
+      const result = detect({
+        filename,
+        code,
+        browserslist: 'IE 11',
+      })
+      const detail = result.details.find(detail => detail.feature === 'big_int')
+      expect(detail?.transformable).toBe(false)
+    })
+  })
 })
 
 describe('es2019', () => {
@@ -120,6 +335,19 @@ describe('es2019', () => {
     })
     expect(result.features.optionalCatchBinding).toBe(true)
   })
+  describe('jsonStrings', () => {
+    const yes = path.join(fixtures, './JsonStrings/should')
+    const no = path.join(fixtures, './JsonStrings/should-not')
+    it('should', async () => {
+      await glob(yes, 'jsonStrings')
+    })
+    it('should-not', async () => {
+      await glob(no, 'jsonStrings', false)
+    })
+    it('detail', async () => {
+      await globDetail(yes, 'json_strings')
+    })
+  })
 })
 
 describe('es2018', () => {
@@ -132,6 +360,50 @@ describe('es2018', () => {
       await glob(spread, 'objectRestSpread', false)
     })
   })
+  describe('asyncGeneratorFunctions', () => {
+    const yes = path.join(fixtures, './AsyncGeneratorFunctions/should')
+    it('should', async () => {
+      await glob(yes, 'asyncGeneratorFunctions')
+    })
+  })
+  describe('forAwait', () => {
+    const yes = path.join(fixtures, './ForAwait/should')
+    it('should', async () => {
+      await glob(yes, 'forAwait')
+    })
+  })
+  describe('dotAllRegex', () => {
+    const yes = path.join(fixtures, './DotAllRegex/should')
+    it('should', async () => {
+      await glob(yes, 'dotAllRegex')
+    })
+  })
+  describe('namedCapturingGroupsRegex', () => {
+    const yes = path.join(fixtures, './NamedCapturingGroupsRegex/should')
+    const no = path.join(fixtures, './NamedCapturingGroupsRegex/should-not')
+    it('should', async () => {
+      await glob(yes, 'namedCapturingGroupsRegex')
+    })
+    it('should-not', async () => {
+      await glob(no, 'namedCapturingGroupsRegex', false)
+    })
+  })
+  describe('unicodePropertyRegex', () => {
+    const yes = path.join(fixtures, './UnicodePropertyRegex/should')
+    const no = path.join(fixtures, './UnicodePropertyRegex/should-not')
+    it('should', async () => {
+      await glob(yes, 'unicodePropertyRegex')
+    })
+    it('should-not', async () => {
+      await glob(no, 'unicodePropertyRegex', false)
+    })
+  })
+  describe('lookbehindRegex', () => {
+    const yes = path.join(fixtures, './LookbehindRegex/should')
+    it('should', async () => {
+      await glob(yes, 'lookbehindRegex')
+    })
+  })
 })
 
 describe('es2017', () => {
@@ -139,6 +411,19 @@ describe('es2017', () => {
   it('asyncToGenerator', async () => {
     await glob(asyncToGenerator, 'asyncToGenerator')
   })
+  describe('trailingFunctionCommas', () => {
+    const yes = path.join(fixtures, './TrailingFunctionCommas/should')
+    const no = path.join(fixtures, './TrailingFunctionCommas/should-not')
+    it('should', async () => {
+      await glob(yes, 'trailingFunctionCommas')
+    })
+    it('should-not', async () => {
+      await glob(no, 'trailingFunctionCommas', false)
+    })
+    it('detail', async () => {
+      await globDetail(yes, 'trailing_function_commas')
+    })
+  })
 })
 
 describe('es2016', () => {
@@ -146,6 +431,9 @@ describe('es2016', () => {
   it('exponentiationOperator', async () => {
     await glob(exponentiationOperator, 'exponentiationOperator')
   })
+  it('exponentiationOperator details', async () => {
+    await globDetail(exponentiationOperator, 'exponentiation_operator')
+  })
 })
 
 describe('es2015', () => {
@@ -189,6 +477,9 @@ describe('es2015', () => {
     it('should', async () => {
       await glob(yes, 'blockScoping')
     })
+    it('details', async () => {
+      await globDetail(yes, 'block_scoping')
+    })
   })
   describe('templateLiterals', () => {
     const yes = path.join(fixtures, './TemplateLiterals/should')
@@ -214,6 +505,34 @@ describe('es2015', () => {
       await glob(yes, 'stickyRegex')
     })
   })
+  describe('unicodeRegex', () => {
+    const yes = path.join(fixtures, './UnicodeRegex/should')
+    it('should', async () => {
+      await glob(yes, 'unicodeRegex')
+    })
+  })
+  describe('binaryLiterals', () => {
+    const yes = path.join(fixtures, './BinaryLiterals/should')
+    it('should', async () => {
+      await glob(yes, 'binaryLiterals')
+    })
+  })
+  describe('octalLiterals', () => {
+    const yes = path.join(fixtures, './OctalLiterals/should')
+    it('should', async () => {
+      await glob(yes, 'octalLiterals')
+    })
+  })
+  describe('unicodeEscapes', () => {
+    const yes = path.join(fixtures, './UnicodeEscapes/should')
+    const no = path.join(fixtures, './UnicodeEscapes/should-not')
+    it('should', async () => {
+      await glob(yes, 'unicodeEscapes')
+    })
+    it('should-not', async () => {
+      await glob(no, 'unicodeEscapes', false)
+    })
+  })
   describe('classes', () => {
     const yes = path.join(fixtures, './Classes/should')
     it('should', async () => {
@@ -228,9 +547,32 @@ describe('es2015', () => {
   })
   describe('typeof_symbol', () => {
     const yes = path.join(fixtures, './TypeOfSymbol/should')
+    const no = path.join(fixtures, './TypeOfSymbol/should-not')
     it('should', async () => {
       await glob(yes, 'typeofSymbol')
     })
+    it('should-not', async () => {
+      await glob(no, 'typeofSymbol', false)
+    })
+  })
+  describe('duplicateKeys', () => {
+    const yes = path.join(fixtures, './DuplicateKeys/should')
+    const no = path.join(fixtures, './DuplicateKeys/should-not')
+    it('should', async () => {
+      await glob(yes, 'duplicateKeys')
+    })
+    it('should-not', async () => {
+      await glob(no, 'duplicateKeys', false)
+    })
+    it('detail', async () => {
+      await globDetail(yes, 'duplicate_keys')
+    })
+  })
+  describe('instanceOf', () => {
+    const yes = path.join(fixtures, './InstanceOf/should')
+    it('should', async () => {
+      await glob(yes, 'instanceOf')
+    })
   })
   describe('objectSuper', () => {
     const yes = path.join(fixtures, './ObjectSuper/should')
@@ -241,6 +583,23 @@ describe('es2015', () => {
     it('should-not', async () => {
       await glob(no, 'objectSuper', false)
     })
+    it('detail', async () => {
+      const files = fg.sync('**.js', {
+        onlyFiles: true,
+        cwd: yes,
+        absolute: true,
+      })
+      for (const filename of files) {
+        const code = (await fs.readFile(filename)).toString('utf-8')
+        const result = detect({
+          filename,
+          code,
+          browserslist: 'IE 11',
+        })
+        const detail = result.details.find(detail => detail.feature === 'object_super')
+        expect(code.slice(detail!.s, detail!.e)).toMatch(/^super\./)
+      }
+    })
   })
   describe('newTarget', () => {
     const yes = path.join(fixtures, './NewTarget/should')
@@ -255,13 +614,75 @@ describe('es2015', () => {
   describe('functionName', () => {
     const yes = path.join(fixtures, './FunctionName/should')
     const no = path.join(fixtures, './FunctionName/should-not')
-    it.todo('should', async () => {
+    it('should', async () => {
       await glob(yes, 'functionName')
     })
-    it.todo('should-not', async () => {
+    it('should-not', async () => {
       await glob(no, 'functionName', false)
     })
   })
+  describe('runtime', () => {
+    const runtime = path.join(fixtures, './Runtime')
+    it('tag', async () => {
+      const files = fg.sync('**.js', {
+        onlyFiles: true,
+        cwd: runtime,
+        absolute: true,
+      })
+      for (const filename of files) {
+        const code = (await fs.readFile(filename)).toString('utf-8')
+        const result = detect({
+          filename,
+          code,
+          browserslist: 'IE 11',
+        })
+        const details = result.details.filter(detail => detail.feature === 'function_name')
+        expect(details.length).toBeGreaterThan(0)
+        expect(details.every(detail => detail.origin === 'runtime')).toBe(true)
+      }
+    })
+    it('skip', async () => {
+      const files = fg.sync('**.js', {
+        onlyFiles: true,
+        cwd: runtime,
+        absolute: true,
+      })
+      for (const filename of files) {
+        const code = (await fs.readFile(filename)).toString('utf-8')
+        const result = detect({
+          filename,
+          code,
+          browserslist: 'IE 11',
+          skipRuntime: true,
+        })
+        expect(result.features.functionName).toBe(false)
+      }
+    })
+    it('modules', async () => {
+      const files = fg.sync('**.js', {
+        onlyFiles: true,
+        cwd: path.join(fixtures, './RuntimeModules'),
+        absolute: true,
+      })
+      for (const filename of files) {
+        const code = (await fs.readFile(filename)).toString('utf-8')
+        const result = detect({
+          filename,
+          code,
+          browserslist: 'IE 11',
+        })
+        const detail = result.details.find(detail => detail.feature === 'nullish_coalescing')
+        expect(detail?.origin).toBeUndefined()
+        const skipped = detect({
+          filename,
+          code,
+          browserslist: 'IE 11',
+          skipRuntime: true,
+        })
+        expect(skipped.features.nullishCoalescing).toBe(true)
+      }
+    })
+  })
   describe('regenerator', () => {
     const yes = path.join(fixtures, './Regenerator/should')
     it('should', async () => {
@@ -269,3 +690,613 @@ describe('es2015', () => {
     })
   })
 })
+
+describe('es5', () => {
+  const es3 = async (cwd: string, feature: string, shouldFound = true) => {
+    const files = fg.sync('**.js', {
+      onlyFiles: true,
+      cwd,
+      absolute: true,
+    })
+    for (const filename of files) {
+      const code = (await fs.readFile(filename)).toString('utf-8')
+      const result = detect({
+        filename,
+        code,
+        browserslist: 'IE 11',
+        target: 'es3',
+      })
+      expect(result.features[feature]).toBe(shouldFound)
+    }
+  }
+  const cases = [
+    ['memberExpressionLiterals', 'MemberExpressionLiterals'],
+    ['propertyLiterals', 'PropertyLiterals'],
+    ['propertyMutators', 'PropertyMutators'],
+    ['trailingLiteralCommas', 'TrailingLiteralCommas'],
+    ['es5Builtins', 'Es5Builtins'],
+  ]
+  for (const [feature, dir] of cases) {
+    describe(feature, () => {
+      it('should', async () => {
+        await es3(path.join(fixtures, dir, 'should'), feature)
+      })
+      it('should-not', async () => {
+        await es3(path.join(fixtures, dir, 'should-not'), feature, false)
+      })
+    })
+  }
+  it('IE 11', async () => {
+    await glob(path.join(fixtures, './PropertyLiterals/should'), 'propertyLiterals', false)
+  })
+  it('browsers without es5 data', async () => {
+    for (const [feature, dir] of cases.slice(2)) {
+      const files = fg.sync('**.js', {
+        onlyFiles: true,
+        cwd: path.join(fixtures, dir, 'should'),
+        absolute: true,
+      })
+      for (const filename of files) {
+        const code = (await fs.readFile(filename)).toString('utf-8')
+        const result = detect({
+          filename,
+          code,
+          browserslist: ['defaults', 'samsung 20', 'op_mob 73', 'and_ff 120'],
+        })
+        expect(result.features[feature]).toBe(false)
+      }
+    }
+  })
+})
+
+describe('builtins', () => {
+  const builtins = async (cwd: string, browserslist: string) => {
+    const files = fg.sync('**.js', {
+      onlyFiles: true,
+      cwd,
+      absolute: true,
+    })
+    const found: string[] = []
+    for (const filename of files) {
+      const code = (await fs.readFile(filename)).toString('utf-8')
+      const result = detect({
+        filename,
+        code,
+        browserslist,
+      })
+      const details = result.details.filter(detail => detail.kind === 'builtin')
+      expect(details.every(detail => detail.e > detail.s)).toBe(true)
+      found.push(...details.map(detail => detail.feature))
+    }
+    return found
+  }
+  const yes = path.join(fixtures, './Builtins/should')
+  const no = path.join(fixtures, './Builtins/should-not')
+  it('should', async () => {
+    const found = await builtins(yes, 'IE 11')
+    expect(found).toEqual(expect.arrayContaining([
+      'Array.prototype.at',
+      'Object.hasOwn',
+      'Promise.allSettled',
+      'String.prototype.replaceAll',
+      'structuredClone',
+    ]))
+  })
+  it('should-not', async () => {
+    expect(await builtins(no, 'IE 11')).toEqual([])
+  })
+  it('supported', async () => {
+    const found = await builtins(yes, 'chrome 100')
+    expect(found).not.toContain('Array.prototype.at')
+    expect(found).not.toContain('Object.hasOwn')
+  })
+  it('low confidence', () => {
+    const result = detect({
+      filename: 'index.js',
+      code: 'list.at(-1); [1, 2].at(-1); Array.prototype.at.call(list, 0); Object.hasOwn(a, "b")',
+      browserslist: 'IE 11',
+    })
+    const confidence = (feature: string) => result.details
+      .filter(detail => detail.feature === feature)
+      .map(detail => detail.lowConfidence ?? false)
+    expect(confidence('Array.prototype.at')).toEqual([true, false, false])
+    expect(confidence('String.prototype.at')).toEqual([true])
+    expect(confidence('Object.hasOwn')).toEqual([false])
+  })
+})
+
+describe('web apis', () => {
+  const webApis = async (cwd: string, browserslist: string, enabled = true) => {
+    const files = fg.sync('**.js', {
+      onlyFiles: true,
+      cwd,
+      absolute: true,
+    })
+    const found: string[] = []
+    for (const filename of files) {
+      const code = (await fs.readFile(filename)).toString('utf-8')
+      const result = detect({
+        filename,
+        code,
+        browserslist,
+        webApis: enabled,
+      })
+      const details = result.details.filter(detail => detail.kind === 'web-api')
+      expect(details.every(detail => detail.e > detail.s && detail.transformable === false)).toBe(true)
+      found.push(...details.map(detail => detail.feature))
+    }
+    return found
+  }
+  const yes = path.join(fixtures, './WebApis/should')
+  const no = path.join(fixtures, './WebApis/should-not')
+  it('should', async () => {
+    const found = await webApis(yes, 'safari 12')
+    expect(found).toEqual(expect.arrayContaining([
+      'ResizeObserver',
+      'IntersectionObserver',
+      'AbortController',
+      'Navigator.clipboard',
+      'Element.replaceChildren',
+    ]))
+  })
+  it('should-not', async () => {
+    expect(await webApis(no, 'IE 11')).toEqual([])
+  })
+  it('supported', async () => {
+    expect(await webApis(yes, 'chrome 100')).toEqual([])
+  })
+  it('disabled', async () => {
+    expect(await webApis(yes, 'IE 11', false)).toEqual([])
+  })
+  it('low confidence', () => {
+    const result = detect({
+      filename: 'index.js',
+      code: 'el.replaceChildren(); navigator.clipboard.writeText(text)',
+      browserslist: 'safari 12',
+      webApis: true,
+    })
+    const confidence = Object.fromEntries(result.details
+      .filter(detail => detail.kind === 'web-api')
+      .map(detail => [detail.feature, detail.lowConfidence ?? false]))
+    expect(confidence).toEqual({
+      'Element.replaceChildren': true,
+      'Navigator.clipboard': false,
+    })
+  })
+})
+
+describe('node', () => {
+  const nodeApis = async (cwd: string, node: string) => {
+    const files = fg.sync('**.js', {
+      onlyFiles: true,
+      cwd,
+      absolute: true,
+    })
+    const found: string[] = []
+    for (const filename of files) {
+      const code = (await fs.readFile(filename)).toString('utf-8')
+      const result = detect({
+        filename,
+        code,
+        browserslist: '',
+        node,
+      })
+      const details = result.details.filter(detail => detail.kind === 'node')
+      expect(details.every(detail => detail.since !== undefined)).toBe(true)
+      found.push(...details.map(detail => `${detail.feature}@${detail.since}`))
+    }
+    return found
+  }
+  const yes = path.join(fixtures, './Node/should')
+  const no = path.join(fixtures, './Node/should-not')
+  it('should', async () => {
+    const found = await nodeApis(yes, '>=14.17.0')
+    expect(found).toEqual(expect.arrayContaining([
+      'node:test@18.0.0',
+      'fs.promises.cp@16.7.0',
+      'AbortSignal.timeout@17.3.0',
+      'Array.prototype.findLast@18.0.0',
+    ]))
+  })
+  it('should-not', async () => {
+    expect(await nodeApis(no, '>=14.17.0')).toEqual([])
+  })
+  it('range', async () => {
+    expect(await nodeApis(yes, '^20 || ^22')).toEqual([])
+    const found = await nodeApis(yes, '>=16.7 <20')
+    expect(found).toContain('node:test@18.0.0')
+    expect(found).not.toContain('fs.promises.cp@16.7.0')
+  })
+  it('invalid range', () => {
+    expect(() => detect({
+      filename: 'index.js',
+      code: '',
+      browserslist: '',
+      node: 'latest',
+    })).toThrow()
+  })
+})
+
+describe('bugfixes', () => {
+  const bugfix = async (cwd: string, feature: string, browserslist: string, bugfixes?: boolean) => {
+    const files = fg.sync('**.js', {
+      onlyFiles: true,
+      cwd,
+      absolute: true,
+    })
+    const found: boolean[] = []
+    for (const filename of files) {
+      const code = (await fs.readFile(filename)).toString('utf-8')
+      const result = detect({
+        filename,
+        code,
+        browserslist,
+        bugfixes,
+      })
+      found.push(result.features[feature])
+    }
+    return found
+  }
+  const cases = [
+    ['bugfixSafariBlockShadowing', 'BugfixSafariBlockShadowing', 'safari 10'],
+    ['bugfixSafariIdDestructuringCollision', 'BugfixSafariIdDestructuringCollision', 'safari 15'],
+    ['bugfixEdgeDefaultParameters', 'BugfixEdgeDefaultParameters', 'edge 17'],
+    ['bugfixTaggedTemplateCaching', 'BugfixTaggedTemplateCaching', 'safari 12'],
+    ['bugfixV8SpreadParametersInOptionalChaining', 'BugfixV8SpreadParametersInOptionalChaining', 'chrome 85'],
+  ]
+  for (const [feature, dir, browserslist] of cases) {
+    describe(feature, () => {
+      it('should', async () => {
+        const found = await bugfix(path.join(fixtures, dir, 'should'), feature, browserslist)
+        expect(found.every(Boolean)).toBe(true)
+      })
+      it('should-not', async () => {
+        const found = await bugfix(path.join(fixtures, dir, 'should-not'), feature, browserslist)
+        expect(found.some(Boolean)).toBe(false)
+      })
+      it('without bugfixes', async () => {
+        const found = await bugfix(path.join(fixtures, dir, 'should'), feature, browserslist, false)
+        expect(found.some(Boolean)).toBe(false)
+      })
+    })
+  }
+  it('fixed engine', async () => {
+    const found = await bugfix(path.join(fixtures, './BugfixTaggedTemplateCaching/should'), 'bugfixTaggedTemplateCaching', 'safari 13')
+    expect(found.some(Boolean)).toBe(false)
+  })
+})
+
+describe('guards', () => {
+  const builtins = async (cwd: string) => {
+    const files = fg.sync('**.js', {
+      onlyFiles: true,
+      cwd,
+      absolute: true,
+    })
+    const details = []
+    for (const filename of files) {
+      const code = (await fs.readFile(filename)).toString('utf-8')
+      const result = detect({
+        filename,
+        code,
+        browserslist: 'IE 11',
+      })
+      details.push(...result.details.filter(detail => detail.kind === 'builtin'))
+    }
+    return details
+  }
+  it('should', async () => {
+    const details = await builtins(path.join(fixtures, './Guards/should'))
+    expect(details.length).toBeGreaterThan(0)
+    expect(details.every(detail => detail.guarded)).toBe(true)
+  })
+  it('should-not', async () => {
+    const details = await builtins(path.join(fixtures, './Guards/should-not'))
+    const unguarded = details.filter(detail => [
+      'Object.hasOwn',
+      'Promise.allSettled',
+      'String.prototype.at',
+      'Array.prototype.flat',
+    ].includes(detail.feature))
+    expect(unguarded.length).toBe(7)
+    expect(unguarded.some(detail => detail.guarded)).toBe(false)
+  })
+})
+
+describe('shadowing', () => {
+  const shadowed = path.join(fixtures, './Shadowed')
+  it('excluded', async () => {
+    const files = fg.sync('**.js', {
+      onlyFiles: true,
+      cwd: shadowed,
+      absolute: true,
+    })
+    for (const filename of files) {
+      const code = (await fs.readFile(filename)).toString('utf-8')
+      const result = detect({
+        filename,
+        code,
+        browserslist: 'IE 11',
+      })
+      const details = result.details.filter(detail => detail.kind === 'builtin')
+      expect(details.every(detail => detail.excluded)).toBe(true)
+      expect(result.features.stickyRegex).toBe(false)
+      expect(result.features.namedCapturingGroupsRegex).toBe(false)
+    }
+  })
+  it('global', () => {
+    const result = detect({
+      filename: 'input.js',
+      code: 'Promise.allSettled(tasks)',
+      browserslist: 'IE 11',
+    })
+    const detail = result.details.find(detail => detail.feature === 'Promise.allSettled')
+    expect(detail).toBeDefined()
+    expect(detail!.excluded).toBeUndefined()
+  })  it('typeof symbol', async () => {
+    const filename = path.join(fixtures, './TypeOfSymbol/should-not/1.js')
+    const code = (await fs.readFile(filename)).toString('utf-8')
+    const result = detect({
+      filename,
+      code,
+      browserslist: 'IE 11',
+    })
+    const detail = result.details.find(detail => detail.feature === 'typeof_symbol')
+    expect(detail?.excluded).toBe(true)
+  })
+})
+
+describe('browserslist', () => {
+  const code = 'a ?? b'
+  it('array', () => {
+    const result = detect({
+      filename: 'input.js',
+      code,
+      browserslist: ['chrome 90', 'safari 13'],
+    })
+    expect(result.features.nullishCoalescing).toBe(true)
+  })
+  it('versions', () => {
+    expect(detect({
+      filename: 'input.js',
+      code,
+      browserslist: { chrome: '79' },
+    }).features.nullishCoalescing).toBe(true)
+    expect(detect({
+      filename: 'input.js',
+      code,
+      browserslist: { chrome: '87', safari: '13.1' },
+    }).features.nullishCoalescing).toBe(false)
+  })
+  it('mobile versions', () => {
+    expect(detect({
+      filename: 'input.js',
+      code,
+      browserslist: { and_chr: '80', ios_saf: '14' },
+    }).features.nullishCoalescing).toBe(false)
+    expect(detect({
+      filename: 'input.js',
+      code,
+      browserslist: { chrome: '100', and_chr: '79' },
+    }).features.nullishCoalescing).toBe(true)
+  })
+  it('invalid', () => {
+    expect(() => detect({
+      filename: 'input.js',
+      code,
+      browserslist: 'not a query',
+    })).toThrow('invalid browserslist')
+    expect(() => detect({
+      filename: 'input.js',
+      code,
+      browserslist: ['chrome 90', 'bogus 1'],
+    })).toThrow("unknown browser: 'bogus'")
+    expect(() => detect({
+      filename: 'input.js',
+      code,
+      browserslist: { chromium: '90' },
+    })).toThrow('invalid browserslist')
+    expect(() => detect({
+      filename: 'input.js',
+      code,
+      browserslist: [],
+    })).toThrow('no browserslist queries')
+  })
+})
+
+describe('browserslist config', () => {
+  const config = path.join(fixtures, './BrowserslistConfig')
+  const code = 'a ?? b'
+  it('.browserslistrc', () => {
+    const result = detect({
+      filename: 'input.js',
+      code,
+      path: path.join(config, './rc/src/index.js'),
+      env: 'production',
+    })
+    expect(result.query).toEqual(['chrome 90', 'safari 14'])
+    expect(result.features.nullishCoalescing).toBe(false)
+  })
+  it('filename', () => {
+    const result = detect({
+      filename: path.join(config, './rc/src/index.js'),
+      code,
+      env: 'production',
+    })
+    expect(result.query).toEqual(['chrome 90', 'safari 14'])
+  })
+  it('package.json', () => {
+    const result = detect({
+      filename: 'input.js',
+      code,
+      path: path.join(config, './package'),
+      env: 'production',
+    })
+    expect(result.query).toEqual(['chrome 79', 'firefox 70'])
+    expect(result.features.nullishCoalescing).toBe(true)
+  })
+  it('env', () => {
+    for (const dir of ['./rc', './package']) {
+      const result = detect({
+        filename: 'input.js',
+        code,
+        path: path.join(config, dir),
+        env: 'legacy',
+      })
+      expect(result.query).toEqual(['IE 11'])
+    }
+  })
+  it('explicit browserslist wins', () => {
+    const result = detect({
+      filename: 'input.js',
+      code,
+      browserslist: 'IE 11',
+      path: path.join(config, './rc'),
+    })
+    expect(result.query).toEqual(['IE 11'])
+  })
+})
+
+describe('include and exclude', () => {
+  const code = 'a ?? b; [].at(-1)'
+  it('include', () => {
+    const result = detect({
+      filename: 'input.js',
+      code,
+      browserslist: 'chrome 100',
+      include: ['transform-nullish-coalescing-operator', 'es.array.at'],
+    })
+    expect(result.features.nullishCoalescing).toBe(true)
+    expect(result.details.some(detail => detail.feature === 'Array.prototype.at')).toBe(true)
+  })
+  it('exclude', () => {
+    const result = detect({
+      filename: 'input.js',
+      code,
+      browserslist: 'IE 11',
+      exclude: ['@babel/plugin-transform-nullish-coalescing-operator', 'es.array.at'],
+    })
+    expect(result.features.nullishCoalescing).toBe(false)
+    expect(result.details.length).toBe(0)
+  })
+  it('forceAllTransforms', () => {
+    const result = detect({
+      filename: 'input.js',
+      code,
+      browserslist: 'chrome 100',
+      forceAllTransforms: true,
+    })
+    expect(result.features.nullishCoalescing).toBe(true)
+  })
+  it('features without a plugin', () => {
+    const include = detect({
+      filename: 'input.js',
+      code: '1n',
+      browserslist: 'chrome 120',
+      include: ['bigInt'],
+    })
+    expect(include.features.bigInt).toBe(true)
+    const exclude = detect({
+      filename: 'input.js',
+      code: '1n',
+      browserslist: 'IE 11',
+      exclude: ['bigInt'],
+    })
+    expect(exclude.features.bigInt).toBe(false)
+  })
+  it('forceAllTransforms skips features that cannot be transformed', () => {
+    const result = detect({
+      filename: 'input.js',
+      code: '1n; a ?? b',
+      browserslist: 'chrome 120',
+      forceAllTransforms: true,
+    })
+    expect(result.features.nullishCoalescing).toBe(true)
+    expect(result.features.bigInt).toBe(false)
+  })
+  it('invalid', () => {
+    expect(() => detect({
+      filename: 'input.js',
+      code,
+      browserslist: 'IE 11',
+      include: ['transform-foo'],
+    })).toThrow('unknown include: transform-foo')
+    expect(() => detect({
+      filename: 'input.js',
+      code,
+      browserslist: 'IE 11',
+      include: ['proposal-optional-chaining'],
+      exclude: ['transform-optional-chaining'],
+    })).toThrow('in both include and exclude')
+    expect(() => detect({
+      filename: 'input.js',
+      code,
+      browserslist: 'IE 11',
+      include: ['bigInt'],
+      exclude: ['bigInt'],
+    })).toThrow('in both include and exclude')
+  })
+})
+
+describe('typescript', () => {
+  const typescript = path.join(fixtures, './TypeScript')
+  const check = async (file: string) => {
+    const filename = path.join(typescript, file)
+    const code = (await fs.readFile(filename)).toString('utf-8')
+    return detect({
+      filename,
+      code,
+      browserslist: 'IE 11',
+    })
+  }
+  it('types', async () => {
+    const result = await check('./types/index.ts')
+    expect(result.details.map(detail => detail.feature)).toEqual(['classes'])
+  })
+  it('tsx', async () => {
+    const result = await check('./types/App.tsx')
+    expect(result.features.nullishCoalescing).toBe(true)
+  })
+  it('syntax', () => {
+    const result = detect({
+      filename: 'input.js',
+      code: 'const a: string = <b>{c ?? d}</b>',
+      browserslist: 'IE 11',
+      syntax: { syntax: 'typescript', tsx: true },
+    })
+    expect(result.features.nullishCoalescing).toBe(true)
+    expect(() => detect({
+      filename: 'input.js',
+      code: 'a',
+      browserslist: 'IE 11',
+      syntax: { syntax: 'flow' },
+    })).toThrow('unknown syntax: flow')
+  })
+  it('tsconfig', () => {
+    const tsconfig = path.join(typescript, './tsconfig/tsconfig.json')
+    const code = 'class A { a = b ?? c }'
+    expect(detect({
+      filename: 'input.ts',
+      code,
+      browserslist: 'chrome 100',
+      tsconfig,
+    }).esVersions.Es2020).toBe(true)
+    expect(detect({
+      filename: 'input.ts',
+      code,
+      browserslist: 'chrome 100',
+      tsconfig,
+      target: 'esnext',
+    }).esVersions).toEqual({})
+  })
+  it('enum', () => {
+    const result = detect({
+      filename: 'input.ts',
+      code: 'enum E { A, B, }\nconst e = [E.A, E.B,]',
+      browserslist: 'IE 11',
+      target: 'es3',
+    })
+    const commas = result.details.filter(detail => detail.feature === 'trailing_literal_commas')
+    expect(commas.map(detail => detail.ls.l)).toEqual([2])
+  })
+})
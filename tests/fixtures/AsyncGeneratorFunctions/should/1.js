@@ -0,0 +1,3 @@
+async function* foo() {
+  yield await bar();
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+class A {
+  async *b() {}
+}
\ No newline at end of file
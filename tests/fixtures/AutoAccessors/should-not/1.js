@@ -0,0 +1,3 @@
+class A {
+  accessor = "a";
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+class A {
+  accessor name = "a";
+}
\ No newline at end of file
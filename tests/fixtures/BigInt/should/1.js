@@ -0,0 +1 @@
+var a = 9007199254740993n;
\ No newline at end of file
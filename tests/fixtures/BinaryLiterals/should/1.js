@@ -0,0 +1 @@
+var flags = 0b1010;
\ No newline at end of file
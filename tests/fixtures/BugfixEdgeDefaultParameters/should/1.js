@@ -0,0 +1,2 @@
+const size = 1
+const grow = (size = 2) => size * 2
\ No newline at end of file
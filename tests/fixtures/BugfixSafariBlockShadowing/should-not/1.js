@@ -0,0 +1,6 @@
+function render(item) {
+  if (item) {
+    let value = normalize(item)
+    draw(value)
+  }
+}
\ No newline at end of file
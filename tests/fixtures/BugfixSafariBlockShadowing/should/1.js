@@ -0,0 +1,6 @@
+function render(item) {
+  if (item) {
+    let item = normalize()
+    draw(item)
+  }
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+const id = 1
+for (const i of list) {
+  const id = i.id
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+const run = function run(run) {
+  return run
+}
\ No newline at end of file
@@ -0,0 +1 @@
+const text = `select * from users`
\ No newline at end of file
@@ -0,0 +1 @@
+const query = sql`select * from users`
\ No newline at end of file
@@ -0,0 +1 @@
+callback?.(args)
\ No newline at end of file
@@ -0,0 +1 @@
+emitter?.emit(...args)
\ No newline at end of file
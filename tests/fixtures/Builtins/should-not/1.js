@@ -0,0 +1,3 @@
+var first = [1, 2, 3].indexOf(1)
+var keys = Object.keys({ a: 1 })
+var text = "a-b-c".replace("-", "_")
\ No newline at end of file
@@ -0,0 +1 @@
+var last = [1, 2, 3].at(-1)
\ No newline at end of file
@@ -0,0 +1 @@
+var has = Object.hasOwn({ a: 1 }, "a")
\ No newline at end of file
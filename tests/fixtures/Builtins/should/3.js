@@ -0,0 +1 @@
+Promise.allSettled([fetch("/a"), fetch("/b")])
\ No newline at end of file
@@ -0,0 +1 @@
+var text = "a-b-c".replaceAll("-", "_")
\ No newline at end of file
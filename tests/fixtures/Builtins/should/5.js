@@ -0,0 +1 @@
+var copy = structuredClone({ a: 1 })
\ No newline at end of file
@@ -0,0 +1 @@
+var copy = window.structuredClone({ a: 1 })
\ No newline at end of file
@@ -0,0 +1 @@
+const A = class {};
\ No newline at end of file
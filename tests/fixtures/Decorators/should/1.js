@@ -0,0 +1,2 @@
+@sealed
+class A {}
\ No newline at end of file
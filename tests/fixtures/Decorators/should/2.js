@@ -0,0 +1,4 @@
+class A {
+  @bound
+  method() {}
+}
\ No newline at end of file
@@ -0,0 +1 @@
+function fun(x = 1, ...rest) {}
\ No newline at end of file
@@ -0,0 +1 @@
+for ([k, v] of map) {}
\ No newline at end of file
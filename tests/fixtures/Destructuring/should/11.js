@@ -0,0 +1 @@
+[a, b] = [b, a];
\ No newline at end of file
@@ -0,0 +1,3 @@
+var obj = {
+  set value({ x }) {}
+};
\ No newline at end of file
@@ -0,0 +1 @@
+var fn = ({ x, y }) => x + y;
\ No newline at end of file
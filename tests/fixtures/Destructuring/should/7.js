@@ -0,0 +1,3 @@
+class A {
+  constructor([x, y]) {}
+}
\ No newline at end of file
@@ -0,0 +1 @@
+try {} catch ({ message }) {}
\ No newline at end of file
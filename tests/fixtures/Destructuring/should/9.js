@@ -0,0 +1 @@
+for (const [k, v] of map) {}
\ No newline at end of file
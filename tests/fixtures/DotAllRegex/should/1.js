@@ -0,0 +1 @@
+const a = /foo.bar/s;
\ No newline at end of file
@@ -0,0 +1 @@
+var re = RegExp(pattern, "s");
\ No newline at end of file
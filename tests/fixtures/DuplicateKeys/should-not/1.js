@@ -0,0 +1,4 @@
+var point = {
+  get x() { return this._x },
+  set x(v) { this._x = v }
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+var point = {
+  x: 1,
+  inner: { x: 2 }
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+var config = {
+  mode: 'a',
+  'mode': 'b'
+}
\ No newline at end of file
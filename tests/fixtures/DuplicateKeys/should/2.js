@@ -0,0 +1,4 @@
+var point = {
+  get x() { return 1 },
+  get x() { return 2 }
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+var list = {
+  1: 'a',
+  '1': 'b'
+}
\ No newline at end of file
@@ -0,0 +1 @@
+const re = /(?<year>\d{4})-(?<month>\d{2})/
\ No newline at end of file
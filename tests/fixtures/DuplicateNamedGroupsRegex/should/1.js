@@ -0,0 +1 @@
+const re = /(?<year>\d{4})-\d{2}|\d{2}-(?<year>\d{4})/
\ No newline at end of file
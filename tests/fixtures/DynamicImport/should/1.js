@@ -0,0 +1 @@
+import("./a.js").then(m => m.default);
\ No newline at end of file
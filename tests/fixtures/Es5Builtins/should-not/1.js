@@ -0,0 +1,2 @@
+var index = 'abc'.indexOf('b')
+var copy = list.slice()
\ No newline at end of file
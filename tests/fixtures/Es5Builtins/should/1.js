@@ -0,0 +1 @@
+var keys = Object.keys(obj)
\ No newline at end of file
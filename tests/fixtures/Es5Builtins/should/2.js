@@ -0,0 +1,3 @@
+if (Array.isArray(list)) {
+  list.forEach(log)
+}
\ No newline at end of file
@@ -0,0 +1,2 @@
+const using = 1
+using(using)
\ No newline at end of file
@@ -0,0 +1,3 @@
+{
+  using file = open()
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+async function main() {
+  await using conn = await connect()
+}
\ No newline at end of file
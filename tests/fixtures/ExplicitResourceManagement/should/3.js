@@ -0,0 +1,3 @@
+for (using lock of locks) {
+  work(lock)
+}
\ No newline at end of file
@@ -0,0 +1 @@
+export * from "./utils";
\ No newline at end of file
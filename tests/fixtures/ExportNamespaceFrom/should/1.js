@@ -0,0 +1 @@
+export * as utils from "./utils";
\ No newline at end of file
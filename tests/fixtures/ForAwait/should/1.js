@@ -0,0 +1,5 @@
+async function foo() {
+  for await (const chunk of stream) {
+    console.log(chunk);
+  }
+}
\ No newline at end of file
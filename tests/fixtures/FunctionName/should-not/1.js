@@ -0,0 +1,3 @@
+var number = function named(x) {
+  return x;
+};
\ No newline at end of file
@@ -0,0 +1 @@
+obj.method = function () {};
\ No newline at end of file
@@ -0,0 +1,3 @@
+var obj = {
+  method: function () {}
+};
\ No newline at end of file
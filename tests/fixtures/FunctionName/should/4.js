@@ -0,0 +1,2 @@
+var fn;
+fn = function () {};
\ No newline at end of file
@@ -0,0 +1,3 @@
+if (typeof Symbol !== 'undefined') {
+  Object.hasOwn(a, 'b')
+}
\ No newline at end of file
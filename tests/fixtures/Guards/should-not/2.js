@@ -0,0 +1,5 @@
+try {
+  Promise.allSettled(tasks)
+} catch (e) {
+  fallback()
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+try {
+  new Function('a ?? b')
+  Promise.allSettled(tasks)
+} catch (e) {
+  fallback()
+}
\ No newline at end of file
@@ -0,0 +1 @@
+const first = 'at' in Array.prototype && 'abc'.at(0)
\ No newline at end of file
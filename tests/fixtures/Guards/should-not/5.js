@@ -0,0 +1,3 @@
+if (options.flat) {
+  [a, [b]].flat()
+}
\ No newline at end of file
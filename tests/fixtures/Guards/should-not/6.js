@@ -0,0 +1,7 @@
+var root
+try {
+  root = Function('return this')()
+} catch (e) {
+  root = window
+}
+Promise.allSettled(tasks)
\ No newline at end of file
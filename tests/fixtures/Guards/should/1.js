@@ -0,0 +1,3 @@
+if (typeof Symbol !== 'undefined' && Symbol.asyncIterator) {
+  iterate(source[Symbol.asyncIterator]())
+}
\ No newline at end of file
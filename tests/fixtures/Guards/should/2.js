@@ -0,0 +1 @@
+const last = Array.prototype.at ? [a, b, c].at(-1) : c
\ No newline at end of file
@@ -0,0 +1,5 @@
+if (!Object.hasOwn) {
+  polyfill()
+} else {
+  Object.hasOwn(a, 'b')
+}
\ No newline at end of file
@@ -0,0 +1 @@
+const clone = typeof structuredClone === 'function' ? structuredClone(value) : copy(value)
\ No newline at end of file
@@ -0,0 +1 @@
+const a = /foo/d;
\ No newline at end of file
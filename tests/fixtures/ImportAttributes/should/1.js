@@ -0,0 +1 @@
+import data from "./data.json" with { type: "json" };
\ No newline at end of file
@@ -0,0 +1 @@
+const data = await import("./data.json", { with: { type: "json" } });
\ No newline at end of file
@@ -0,0 +1 @@
+const url = new URL("./a.png", import.meta.url);
\ No newline at end of file
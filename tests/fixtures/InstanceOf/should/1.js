@@ -0,0 +1,3 @@
+if (err instanceof Error) {
+  throw err
+}
\ No newline at end of file
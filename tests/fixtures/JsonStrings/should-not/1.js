@@ -0,0 +1 @@
+var escaped = "line\u2028separator"
\ No newline at end of file
@@ -0,0 +1 @@
+var template = `line separator`
\ No newline at end of file
@@ -0,0 +1 @@
+var separator = "line separator"
\ No newline at end of file
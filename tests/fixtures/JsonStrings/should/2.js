@@ -0,0 +1 @@
+var separator = 'paragraph separator'
\ No newline at end of file
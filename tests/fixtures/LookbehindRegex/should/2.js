@@ -0,0 +1 @@
+const a = /(?<!\$)\d+/;
\ No newline at end of file
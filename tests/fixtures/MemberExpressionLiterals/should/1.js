@@ -0,0 +1,3 @@
+promise.catch(function (err) {
+  log(err)
+})
\ No newline at end of file
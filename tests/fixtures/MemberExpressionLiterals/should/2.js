@@ -0,0 +1 @@
+var value = options.default
\ No newline at end of file
@@ -0,0 +1 @@
+const a = /[(?<year>]\d{4}/;
\ No newline at end of file
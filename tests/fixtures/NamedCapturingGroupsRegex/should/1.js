@@ -0,0 +1 @@
+const a = /(?<year>\d{4})-(?<month>\d{2})/;
\ No newline at end of file
@@ -0,0 +1 @@
+var re = new RegExp("(?<year>\\d{4})", "y");
\ No newline at end of file
@@ -0,0 +1 @@
+console.log(import.meta.url);
\ No newline at end of file
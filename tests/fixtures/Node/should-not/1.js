@@ -0,0 +1,4 @@
+const fs = require("fs")
+const path = require("path")
+
+fs.readFileSync(path.join(__dirname, "a.txt"))
\ No newline at end of file
@@ -0,0 +1,3 @@
+import { test } from "node:test"
+
+test("works", () => {})
\ No newline at end of file
@@ -0,0 +1,3 @@
+const fs = require("fs")
+
+fs.promises.cp("src", "dist", { recursive: true })
\ No newline at end of file
@@ -0,0 +1 @@
+const signal = AbortSignal.timeout(1000)
\ No newline at end of file
@@ -0,0 +1 @@
+const last = [1, 2, 3].findLast(n => n > 1)
\ No newline at end of file
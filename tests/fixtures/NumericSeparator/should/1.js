@@ -0,0 +1 @@
+var budget = 1_000_000_000;
\ No newline at end of file
@@ -0,0 +1 @@
+var mask = 0b1010_0001;
\ No newline at end of file
@@ -0,0 +1,5 @@
+const B = class extends A {
+  say() {
+    return super.say()
+  }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+Object.setPrototypeOf(child, {
+  greet() {
+    return super.greet() + "!"
+  },
+})
\ No newline at end of file
@@ -0,0 +1,5 @@
+module.exports = {
+  get name() {
+    return super.name
+  },
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+function create() {
+  return {
+    toString() {
+      return `[${super.toString()}]`
+    },
+  }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+var mode = 0o755;
\ No newline at end of file
@@ -0,0 +1 @@
+var fn = (x) => x;
\ No newline at end of file
@@ -0,0 +1 @@
+var fn = (x = 1) => x;
\ No newline at end of file
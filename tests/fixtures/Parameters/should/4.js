@@ -0,0 +1 @@
+var fn = (...args) => args;
\ No newline at end of file
@@ -0,0 +1,3 @@
+class A {
+  constructor(x = 1) {}
+}
\ No newline at end of file
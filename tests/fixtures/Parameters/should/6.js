@@ -0,0 +1,3 @@
+var obj = {
+  set value(x = 1) {}
+};
\ No newline at end of file
@@ -0,0 +1,3 @@
+class A {
+  #inc() {}
+}
\ No newline at end of file
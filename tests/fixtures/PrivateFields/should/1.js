@@ -0,0 +1,3 @@
+class A {
+  #count = 0;
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+class A {
+  #brand;
+  static is(obj) {
+    return #brand in obj;
+  }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+var exports = {
+  'default': main
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+var exports = {
+  default: main,
+  class: 'btn'
+}
\ No newline at end of file
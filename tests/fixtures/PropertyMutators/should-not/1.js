@@ -0,0 +1,3 @@
+var user = {
+  name: 'a'
+}
\ No newline at end of file
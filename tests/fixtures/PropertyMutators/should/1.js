@@ -0,0 +1,5 @@
+var user = {
+  get name() {
+    return this._name
+  }
+}
\ No newline at end of file
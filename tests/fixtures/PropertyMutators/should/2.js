@@ -0,0 +1,5 @@
+var user = {
+  set name(value) {
+    this._name = value
+  }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+const a = /^(?:a)(?=b)(?!c)$/;
\ No newline at end of file
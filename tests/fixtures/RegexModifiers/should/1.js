@@ -0,0 +1 @@
+const a = /^(?i:a)b$/;
\ No newline at end of file
@@ -0,0 +1,5 @@
+var __defProp = Object.defineProperty;
+var __export = function (target, all) {
+  for (var name in all)
+    __defProp(target, name, { get: all[name], enumerable: true });
+};
\ No newline at end of file
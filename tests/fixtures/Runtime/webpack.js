@@ -0,0 +1,18 @@
+/******/ (() => { // webpackBootstrap
+/******/ 	var __webpack_modules__ = ({});
+/******/ 	var __webpack_module_cache__ = {};
+/******/ 	function __webpack_require__(moduleId) {
+/******/ 		var module = __webpack_module_cache__[moduleId] = { exports: {} };
+/******/ 		__webpack_modules__[moduleId](module, module.exports, __webpack_require__);
+/******/ 		return module.exports;
+/******/ 	}
+/******/ 	(() => {
+/******/ 		__webpack_require__.n = (module) => {
+/******/ 			var getter = module && module.__esModule ?
+/******/ 				function () { return module["default"]; } :
+/******/ 				function () { return module; };
+/******/ 			var wrapped = { a: function () { return getter; } };
+/******/ 			return wrapped;
+/******/ 		};
+/******/ 	})();
+/******/ })();
\ No newline at end of file
@@ -0,0 +1,30 @@
+/******/ (function(modules) { // webpackBootstrap
+/******/ 	var installedModules = {};
+/******/ 	function __webpack_require__(moduleId) {
+/******/ 		if(installedModules[moduleId]) {
+/******/ 			return installedModules[moduleId].exports;
+/******/ 		}
+/******/ 		var module = installedModules[moduleId] = { i: moduleId, l: false, exports: {} };
+/******/ 		modules[moduleId].call(module.exports, module, module.exports, __webpack_require__);
+/******/ 		module.l = true;
+/******/ 		return module.exports;
+/******/ 	}
+/******/ 	__webpack_require__.m = modules;
+/******/ 	__webpack_require__.c = installedModules;
+/******/ 	__webpack_require__.n = function(module) {
+/******/ 		var getter = module && module.__esModule ?
+/******/ 			function getDefault() { return module["default"]; } :
+/******/ 			function getModuleExports() { return module; };
+/******/ 		return getter;
+/******/ 	};
+/******/ 	return __webpack_require__(__webpack_require__.s = 0);
+/******/ })
+/************************************************************************/
+/******/ ([
+/* 0 */
+/***/ (function(module, exports) {
+
+var value = options.value ?? 1;
+
+/***/ })
+/******/ ]);
\ No newline at end of file
@@ -0,0 +1,28 @@
+/******/ (() => { // webpackBootstrap
+/******/ 	var __webpack_modules__ = ({
+
+/***/ "./src/index.js":
+/***/ ((module) => {
+
+var value = options.value ?? 1;
+
+/***/ })
+
+/******/ 	});
+/******/ 	var __webpack_module_cache__ = {};
+/******/ 	function __webpack_require__(moduleId) {
+/******/ 		var module = __webpack_module_cache__[moduleId] = { exports: {} };
+/******/ 		__webpack_modules__[moduleId](module, module.exports, __webpack_require__);
+/******/ 		return module.exports;
+/******/ 	}
+/******/ 	__webpack_require__.m = __webpack_modules__;
+/******/ 	(() => {
+/******/ 		__webpack_require__.n = (module) => {
+/******/ 			var getter = module && module.__esModule ?
+/******/ 				function () { return module["default"]; } :
+/******/ 				function () { return module; };
+/******/ 			return getter;
+/******/ 		};
+/******/ 	})();
+/******/ 	var __webpack_exports__ = __webpack_require__("./src/index.js");
+/******/ })();
\ No newline at end of file
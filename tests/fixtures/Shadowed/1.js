@@ -0,0 +1,2 @@
+var Promise = require('es6-promise')
+Promise.allSettled(tasks)
\ No newline at end of file
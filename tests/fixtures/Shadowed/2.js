@@ -0,0 +1,3 @@
+function copy(structuredClone, value) {
+  return structuredClone(value)
+}
\ No newline at end of file
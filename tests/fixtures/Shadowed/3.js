@@ -0,0 +1,2 @@
+import { Map } from 'immutable'
+const map = new Map()
\ No newline at end of file
@@ -0,0 +1,2 @@
+const RegExp = XRegExp
+const re = new RegExp('(?<year>\\d{4})', 'y')
\ No newline at end of file
@@ -0,0 +1,4 @@
+class A {
+  #count = 0;
+  static count = 0;
+}
\ No newline at end of file
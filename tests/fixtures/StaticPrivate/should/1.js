@@ -0,0 +1,3 @@
+class A {
+  static #instances = 0;
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+class A {
+  static #create() {}
+}
\ No newline at end of file
@@ -0,0 +1 @@
+var re = new RegExp("o+", "y");
\ No newline at end of file
@@ -0,0 +1,4 @@
+async function load() {
+  await fetch("/api");
+}
+const f = async () => await load();
\ No newline at end of file
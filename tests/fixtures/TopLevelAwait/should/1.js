@@ -0,0 +1 @@
+const data = await fetch("/api");
\ No newline at end of file
@@ -0,0 +1 @@
+for await (const chunk of stream) {}
\ No newline at end of file
@@ -0,0 +1,4 @@
+var arr = [1, 2,]
+var obj = { a: 1, }
+var re = /[,)]/
+console.log(arr, obj, re)
\ No newline at end of file
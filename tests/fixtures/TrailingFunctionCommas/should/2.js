@@ -0,0 +1,4 @@
+console.log(
+  1,
+  2,
+)
\ No newline at end of file
@@ -0,0 +1 @@
+var add = (a, b,) => a + b
\ No newline at end of file
@@ -0,0 +1,3 @@
+var list = [1, 2, 3]
+var user = { name: 'a' }
+call(a, b)
\ No newline at end of file
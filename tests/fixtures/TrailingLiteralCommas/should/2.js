@@ -0,0 +1 @@
+var list = [1, 2, 3,]
\ No newline at end of file
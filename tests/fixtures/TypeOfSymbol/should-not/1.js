@@ -0,0 +1,3 @@
+function isSymbol(Symbol) {
+  return typeof Symbol() === 'symbol'
+}
\ No newline at end of file
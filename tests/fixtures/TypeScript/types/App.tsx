@@ -0,0 +1,3 @@
+export function App(props: { title?: string }) {
+  return <h1>{props.title ?? 'untitled'}</h1>
+}
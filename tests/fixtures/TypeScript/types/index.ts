@@ -0,0 +1,27 @@
+import type { Options } from './options'
+import { type Plugin, register } from './plugin'
+
+export interface Config {
+  plugins: Plugin[],
+  options?: Options,
+}
+
+export type Callback = (error: Error | null, result?: string,) => void
+
+declare const VERSION: string
+declare global {
+  interface Window { config: Config }
+}
+
+export abstract class Base implements Plugin {
+  declare name: string
+  [key: string]: unknown
+  abstract apply(config: Config): void
+}
+
+export function load(path: string): Config
+export function load(path: string, callback: Callback): void
+export function load(this: Window, path: string, callback?: Callback): Config | void {
+  var config = <Config>register(path as unknown as Options)
+  return callback ? callback(null, path) : config!
+}
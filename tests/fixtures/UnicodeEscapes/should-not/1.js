@@ -0,0 +1 @@
+var a = "é\\u{41}";
\ No newline at end of file
@@ -0,0 +1 @@
+var smile = "\u{1F600}";
\ No newline at end of file
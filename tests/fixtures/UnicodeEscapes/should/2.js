@@ -0,0 +1 @@
+var \u{61}bc = 1;
\ No newline at end of file
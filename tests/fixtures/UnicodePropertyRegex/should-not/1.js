@@ -0,0 +1 @@
+const a = /\p{L}/;
\ No newline at end of file
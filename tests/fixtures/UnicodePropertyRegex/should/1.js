@@ -0,0 +1 @@
+const a = /\p{Script=Greek}+/u;
\ No newline at end of file
@@ -0,0 +1 @@
+const a = /\u{1F600}/u;
\ No newline at end of file
@@ -0,0 +1 @@
+var re = /[a-z]/u;
\ No newline at end of file
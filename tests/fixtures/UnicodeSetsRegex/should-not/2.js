@@ -0,0 +1 @@
+var re = new RegExp(pattern, flags);
\ No newline at end of file
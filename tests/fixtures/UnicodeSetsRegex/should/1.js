@@ -0,0 +1 @@
+var re = new RegExp("[\\p{L}--[a-z]]", "v");
\ No newline at end of file
@@ -0,0 +1,4 @@
+var el = document.getElementById("app")
+el.addEventListener("click", function () {
+  console.log(navigator.userAgent)
+})
\ No newline at end of file
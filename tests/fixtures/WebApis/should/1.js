@@ -0,0 +1,3 @@
+var observer = new ResizeObserver(function (entries) {
+  console.log(entries)
+})
\ No newline at end of file
@@ -0,0 +1,2 @@
+var controller = new AbortController()
+fetch("/api", { signal: controller.signal })
\ No newline at end of file
@@ -0,0 +1 @@
+navigator.clipboard.writeText("copied")
\ No newline at end of file
@@ -0,0 +1 @@
+document.body.replaceChildren()
\ No newline at end of file